@@ -9,7 +9,7 @@ mod tests {
     fn bench1(b: &mut test::Bencher) {
         let w = World::init(10000, 10, 10, Corner, true);
         let patches = w.borrow().patches();
-        let n = w.borrow().patches().one_of().neighbors();
+        let n = w.borrow().patches().one_of().unwrap().neighbors();
         let turtles = w.borrow().turtles();
         b.iter(|| {
             turtles.ask(|t| {
@@ -31,7 +31,7 @@ impl Model {
     pub fn setup(&mut self) {
         self.patches.own(vec!["type", "payoff"]);
         self.patches.ask(|p| {
-            p.set("type", if fastrand::f64() <= 0.5 { "c" } else { "d" });
+            p.set("type", if p.random_float(1.) <= 0.5 { "c" } else { "d" });
             p.set("payoff", 0);
         });
     }
@@ -0,0 +1,161 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields};
+
+// fields whose get_/set_ accessors would be shadowed by a built-in agent method
+const RESERVED: &[&str] = &[
+    "activation",
+    "bool",
+    "breed",
+    "color",
+    "f64",
+    "heading",
+    "i64",
+    "pcolor",
+    "random_color",
+    "random_pcolor",
+    "rng_state",
+    "shape",
+    "size",
+    "str",
+    "tie_mode",
+    "w",
+    "xcor",
+    "ycor",
+];
+
+#[proc_macro_derive(TurtleVars)]
+pub fn derive_turtle_vars(input: TokenStream) -> TokenStream {
+    derive_vars(input, quote!(::ruscape::prelude::TurtleRef))
+}
+
+#[proc_macro_derive(PatchVars)]
+pub fn derive_patch_vars(input: TokenStream) -> TokenStream {
+    derive_vars(input, quote!(::ruscape::prelude::PatchRef))
+}
+
+#[proc_macro_derive(LinkVars)]
+pub fn derive_link_vars(input: TokenStream) -> TokenStream {
+    derive_vars(input, quote!(::ruscape::prelude::LinkRef))
+}
+
+fn derive_vars(input: TokenStream, agent_ref: TokenStream2) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let vis = &input.vis;
+    if !input.generics.params.is_empty() {
+        return Error::new_spanned(&input.generics, "agent variables cannot be generic")
+            .to_compile_error()
+            .into();
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Error::new_spanned(name, "agent variables need named fields")
+                    .to_compile_error()
+                    .into()
+            }
+        },
+        _ => {
+            return Error::new_spanned(name, "agent variables must be a struct")
+                .to_compile_error()
+                .into()
+        }
+    };
+
+    if let Some(field) = fields
+        .iter()
+        .find(|field| RESERVED.contains(&field.ident.as_ref().unwrap().to_string().as_str()))
+    {
+        return Error::new_spanned(
+            &field.ident,
+            "this name clashes with a built-in agent method, rename the field",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let idents = fields
+        .iter()
+        .map(|field| field.ident.clone().unwrap())
+        .collect::<Vec<_>>();
+    let types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+    let keys = idents
+        .iter()
+        .map(|ident| ident.to_string())
+        .collect::<Vec<_>>();
+    let getters = idents
+        .iter()
+        .map(|ident| format_ident!("get_{}", ident))
+        .collect::<Vec<_>>();
+    let setters = idents
+        .iter()
+        .map(|ident| format_ident!("set_{}", ident))
+        .collect::<Vec<_>>();
+    let access = format_ident!("{}Vars", name);
+
+    let expanded = quote! {
+        impl ::ruscape::prelude::AgentVars for #name {
+            fn var_names(&self) -> &'static [&'static str] {
+                &[#(#keys),*]
+            }
+            fn get_var(&self, key: &str) -> Option<::ruscape::prelude::Variable> {
+                match key {
+                    #(#keys => ::ruscape::prelude::var_from(
+                        ::std::clone::Clone::clone(&self.#idents),
+                    ).ok(),)*
+                    _ => None,
+                }
+            }
+            fn set_var(
+                &mut self,
+                key: &str,
+                value: ::ruscape::prelude::Variable,
+            ) -> Option<Result<(), ::ruscape::prelude::RuscapeError>> {
+                match key {
+                    #(#keys => Some(
+                        ::ruscape::prelude::convert_var::<#types>(value)
+                            .map(|value| self.#idents = value),
+                    ),)*
+                    _ => None,
+                }
+            }
+            fn clone_vars(&self) -> Box<dyn ::ruscape::prelude::AgentVars> {
+                Box::new(::std::clone::Clone::clone(self))
+            }
+            fn as_any(&self) -> &dyn ::std::any::Any {
+                self
+            }
+            fn as_any_mut(&mut self) -> &mut dyn ::std::any::Any {
+                self
+            }
+        }
+
+        #vis trait #access {
+            #(
+                fn #getters(&self) -> #types;
+                fn #setters(&self, value: #types);
+            )*
+        }
+
+        impl #access for #agent_ref {
+            #(
+                fn #getters(&self) -> #types {
+                    ::std::clone::Clone::clone(&self.vars::<#name>().#idents)
+                }
+                fn #setters(&self, value: #types) {
+                    if self.is_syncing() {
+                        let value = ::ruscape::prelude::var_from(value)
+                            .unwrap_or_else(|e| panic!("{}", e));
+                        self.set(#keys, value);
+                    } else {
+                        self.vars_mut::<#name>().#idents = value;
+                    }
+                }
+            )*
+        }
+    };
+    expanded.into()
+}
@@ -0,0 +1,50 @@
+use crate::LinkSet::LinkSet;
+use crate::PatchSet::PatchSet;
+use crate::TurtleSet::TurtleSet;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Activation {
+    Sequential,
+    Random,
+    RandomWithReplacement,
+    // ascending by a numeric variable, agents without it go last
+    SortedBy(&'static str),
+}
+
+impl Default for Activation {
+    fn default() -> Self {
+        Activation::Random
+    }
+}
+
+pub trait HasActivation {
+    fn activation(&self) -> Option<Activation>;
+    fn set_activation(&mut self, activation: Option<Activation>);
+}
+
+impl HasActivation for TurtleSet {
+    fn activation(&self) -> Option<Activation> {
+        self.activation
+    }
+    fn set_activation(&mut self, activation: Option<Activation>) {
+        self.activation = activation;
+    }
+}
+
+impl HasActivation for PatchSet {
+    fn activation(&self) -> Option<Activation> {
+        self.activation
+    }
+    fn set_activation(&mut self, activation: Option<Activation>) {
+        self.activation = activation;
+    }
+}
+
+impl HasActivation for LinkSet {
+    fn activation(&self) -> Option<Activation> {
+        self.activation
+    }
+    fn set_activation(&mut self, activation: Option<Activation>) {
+        self.activation = activation;
+    }
+}
@@ -1,30 +1,165 @@
+use crate::AgentVars::AgentVars;
+use crate::Error::RuscapeError;
+use crate::GetRng::agent_seed;
 use crate::World::World;
 use fastrand::*;
 
-use crate::MapType::VariableMap;
-use crate::Variable::Variable;
+use crate::MapType::{VariableMap, VariableSchema};
+use crate::VarKey::VarKey;
+use crate::Variable::{VarType, Variable};
 use std::cell::RefCell;
 use std::cmp::Ordering;
+use std::convert::TryFrom;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
+use std::hash::Hash;
 use std::ops::{Deref, DerefMut};
 use std::rc::Rc;
 
 pub trait Agent: Clone + Deref<Target = VariableMap> + DerefMut<Target = VariableMap> {
-    type IDX: PartialEq + PartialOrd;
+    type IDX: PartialEq + PartialOrd + Debug;
     fn who(&self) -> Self::IDX;
     fn get(&self, key: &str) -> Variable {
-        self.deref().get(key).unwrap().clone()
+        self.try_get(key).unwrap_or_else(|e| panic!("{}", e))
+    }
+    fn try_get(&self, key: &str) -> Result<Variable, RuscapeError> {
+        self.check_alive()?;
+        self.deref()
+            .get(key)
+            .cloned()
+            .or_else(|| self.vars().and_then(|vars| vars.get_var(key)))
+            .ok_or_else(|| self.unknown_variable(key))
     }
     fn set<T: Into<Variable>>(&mut self, key: &str, value: T) {
-        *self.get_mut(key).unwrap() = value.into();
+        self.try_set(key, value).unwrap_or_else(|e| panic!("{}", e))
+    }
+    fn try_set<T: Into<Variable>>(&mut self, key: &str, value: T) -> Result<(), RuscapeError> {
+        self.check_alive()?;
+        let value = value.into();
+        let key = match self.get_key_value(key) {
+            Some((key, _)) => key.clone(),
+            // typed fields are checked against a copy and buffered like any other write
+            None if self.is_syncing() => {
+                self.vars()
+                    .and_then(|vars| vars.clone_vars().set_var(key, value.clone()))
+                    .unwrap_or_else(|| Err(self.unknown_variable(key)))
+                    .map_err(|e| self.type_mismatch(key, e))?;
+                VarKey::new(key)
+            }
+            None => {
+                let set = match self.vars_mut() {
+                    Some(vars) => vars.set_var(key, value),
+                    None => None,
+                };
+                return set
+                    .unwrap_or_else(|| Err(self.unknown_variable(key)))
+                    .map_err(|e| self.type_mismatch(key, e));
+            }
+        };
+        if let Some(&expected) = self.schema().get(&key) {
+            if !expected.accepts(&value) {
+                return Err(RuscapeError::TypeMismatch {
+                    agent: format!("{:?}", self.who()),
+                    variable: key.to_string(),
+                    expected,
+                    found: value.var_type(),
+                });
+            }
+        }
+        match self.shadow_mut() {
+            Some(shadow) => shadow.insert(key, value),
+            None => self.insert(key, value),
+        };
+        Ok(())
+    }
+    fn own_typed<K: Into<VarKey>, T: Into<Variable>>(
+        &mut self,
+        key: K,
+        var_type: VarType,
+        default: T,
+    ) -> Result<(), RuscapeError> {
+        let key = key.into();
+        let default = default.into();
+        if !var_type.accepts(&default) {
+            return Err(RuscapeError::TypeMismatch {
+                agent: format!("{:?}", self.who()),
+                variable: key.to_string(),
+                expected: var_type,
+                found: default.var_type(),
+            });
+        }
+        self.schema_mut().insert(key.clone(), var_type);
+        self.insert(key, default);
+        Ok(())
+    }
+    fn type_mismatch(&self, key: &str, e: RuscapeError) -> RuscapeError {
+        match e {
+            RuscapeError::WrongType { expected, found } => RuscapeError::TypeMismatch {
+                agent: format!("{:?}", self.who()),
+                variable: key.to_string(),
+                expected,
+                found,
+            },
+            e => e,
+        }
+    }
+    fn unknown_variable(&self, key: &str) -> RuscapeError {
+        RuscapeError::UnknownVariable {
+            agent: format!("{:?}", self.who()),
+            variable: key.to_string(),
+        }
+    }
+    fn world(&self) -> Rc<RefCell<World>> {
+        self.try_world().unwrap_or_else(|e| panic!("{}", e))
+    }
+    fn try_world(&self) -> Result<Rc<RefCell<World>>, RuscapeError>;
+    fn is_alive(&self) -> bool {
+        true
+    }
+    fn check_alive(&self) -> Result<(), RuscapeError> {
+        if self.is_alive() {
+            Ok(())
+        } else {
+            Err(RuscapeError::DeadAgent(format!("{:?}", self.who())))
+        }
+    }
+    fn shadow(&self) -> &Option<VariableMap>;
+    fn shadow_mut(&mut self) -> &mut Option<VariableMap>;
+    fn schema(&self) -> &VariableSchema;
+    fn schema_mut(&mut self) -> &mut VariableSchema;
+    fn vars(&self) -> Option<&dyn AgentVars>;
+    fn vars_mut(&mut self) -> &mut Option<Box<dyn AgentVars>>;
+    fn is_syncing(&self) -> bool {
+        self.shadow().is_some()
+    }
+    fn begin_sync(&mut self) {
+        *self.shadow_mut() = Some(VariableMap::default());
+    }
+    fn commit_sync(&mut self) {
+        if let Some(shadow) = self.shadow_mut().take() {
+            for (key, value) in shadow {
+                if let Some(slot) = self.get_mut(&key) {
+                    *slot = value;
+                } else if let Some(vars) = self.vars_mut() {
+                    let _ = vars.set_var(&key, value);
+                }
+            }
+        }
+    }
+    fn abort_sync(&mut self) {
+        *self.shadow_mut() = None;
+    }
+    // agent references in variables are strong, so dropping them breaks agent cycles
+    fn clear_variables(&mut self) {
+        self.clear();
+        *self.vars_mut() = None;
+        *self.shadow_mut() = None;
     }
-    fn world(&self) -> Rc<RefCell<World>>;
 }
 
 #[derive(Default)]
 pub struct AgentRef<A: Agent> {
-    rng: Rng,
+    rng: Rc<Rng>,
     pub(crate) agent_ref: Rc<RefCell<A>>,
 }
 
@@ -32,12 +167,47 @@ impl<A: Agent> AgentRef<A> {
     pub fn rng(&self) -> &Rng {
         &self.rng
     }
+    pub fn ticks(&self) -> Result<f64, RuscapeError> {
+        self.borrow().world().borrow().ticks()
+    }
+    pub fn is_alive(&self) -> bool {
+        self.borrow().is_alive()
+    }
+    pub(crate) fn rng_state(&self) -> u64 {
+        self.rng.get_seed()
+    }
+    pub(crate) fn set_rng_state(&self, state: u64) {
+        self.rng.seed(state);
+    }
+}
+
+impl<A: Agent> AgentRef<A>
+where
+    A::IDX: Hash,
+{
+    pub(crate) fn seed_rng(&self, world_seed: u64) {
+        self.rng.seed(agent_seed(world_seed, &self.borrow().who()));
+    }
+}
+
+impl<A: Agent> AgentRef<A>
+where
+    A::IDX: Debug,
+{
+    pub fn check_alive(&self) -> Result<(), RuscapeError> {
+        self.borrow().check_alive()
+    }
+    pub(crate) fn assert_alive(&self) {
+        if let Err(e) = self.check_alive() {
+            panic!("{}", e)
+        }
+    }
 }
 
 impl<A: Agent> AgentRef<A> {
     pub fn new(a: A) -> AgentRef<A> {
         AgentRef {
-            rng: Rng::new(),
+            rng: Rc::new(Rng::new()),
             agent_ref: Rc::new(RefCell::new(a)),
         }
     }
@@ -52,7 +222,7 @@ impl<A: Agent + Debug> Debug for AgentRef<A> {
 impl<A: Agent> Clone for AgentRef<A> {
     fn clone(&self) -> Self {
         AgentRef {
-            rng: Rng::new(),
+            rng: Rc::clone(&self.rng),
             agent_ref: Rc::clone(&self.agent_ref),
         }
     }
@@ -97,11 +267,49 @@ impl<A: Agent> Ord for AgentRef<A> {
 
 impl<A: Agent> AgentRef<A> {
     #[inline]
-    pub fn get(&self, key: &'static str) -> Variable {
+    pub fn get(&self, key: &str) -> Variable {
         self.agent_ref.borrow().get(key)
     }
     #[inline]
-    pub fn set<T: Into<Variable>>(&self, key: &'static str, value: T) {
-        *self.agent_ref.borrow_mut().get_mut(key).unwrap() = value.into();
+    pub fn set<T: Into<Variable>>(&self, key: &str, value: T) {
+        self.agent_ref.borrow_mut().set(key, value);
+    }
+    #[inline]
+    pub fn is_syncing(&self) -> bool {
+        self.agent_ref.borrow().is_syncing()
+    }
+    #[inline]
+    pub fn try_get(&self, key: &str) -> Result<Variable, RuscapeError> {
+        self.agent_ref.borrow().try_get(key)
+    }
+    #[inline]
+    pub fn try_set<T: Into<Variable>>(&self, key: &str, value: T) -> Result<(), RuscapeError> {
+        self.agent_ref.borrow_mut().try_set(key, value)
+    }
+    pub fn try_get_as<T>(&self, key: &str) -> Result<T, RuscapeError>
+    where
+        T: TryFrom<Variable, Error = RuscapeError>,
+    {
+        T::try_from(self.try_get(key)?).map_err(|e| self.borrow().type_mismatch(key, e))
+    }
+    #[inline]
+    pub fn get_f64(&self, key: &str) -> f64 {
+        self.try_get_as(key).unwrap_or_else(|e| panic!("{}", e))
+    }
+    #[inline]
+    pub fn get_i64(&self, key: &str) -> i64 {
+        self.try_get_as(key).unwrap_or_else(|e| panic!("{}", e))
+    }
+    #[inline]
+    pub fn get_bool(&self, key: &str) -> bool {
+        self.try_get_as(key).unwrap_or_else(|e| panic!("{}", e))
+    }
+    #[inline]
+    pub fn get_str(&self, key: &str) -> String {
+        self.try_get_as(key).unwrap_or_else(|e| panic!("{}", e))
+    }
+    #[inline]
+    pub fn try_world(&self) -> Result<Rc<RefCell<World>>, RuscapeError> {
+        self.agent_ref.borrow().try_world()
     }
 }
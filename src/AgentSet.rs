@@ -1,16 +1,22 @@
+use crate::Activation::{Activation, HasActivation};
 use crate::Agent::{Agent, AgentRef};
-use crate::GetRng::GetRng;
+use crate::AgentVars::AgentVars;
+use crate::Commands::CommandBuffer;
+use crate::Error::RuscapeError;
+use crate::GetRng::{GetRng, RandRng};
 use itertools::Itertools;
 
 use crate::LinkSet::LinkSet;
 use crate::PatchSet::PatchSet;
 use crate::TurtleSet::TurtleSet;
+use crate::VarKey::VarKey;
+use crate::Variable::{VarType, Variable};
 use crate::World::{World, WorldRef};
 
 use std::cell::RefCell;
 
 use crate::MapType::HashMap;
-use rand::{thread_rng, Rng};
+use rand::Rng;
 
 use std::fmt::Debug;
 use std::hash::Hash;
@@ -24,49 +30,58 @@ pub trait AgentSet<A: Agent>:
     + Deref<Target = HashMap<A::IDX, AgentRef<A>>>
     + DerefMut<Target = HashMap<A::IDX, AgentRef<A>>>
     + HasWorld
+    + HasActivation
     + Debug
     + Clone
 where
     A::IDX: Eq + Hash + Copy + Debug,
 {
+    // dead members stay in the map until they are removed, so the defaults below go through
+    // this live view and count() is the size of the set
+    fn live(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&idx| self[idx].is_alive())
+            .collect()
+    }
+
     fn count(&self) -> usize {
-        self.len()
+        self.values().filter(|agent| agent.is_alive()).count()
     }
 
     fn any<F>(&self, key: F) -> bool
     where
         F: Fn(&AgentRef<A>) -> bool,
     {
-        self.values().any(|x| key(x))
+        self.values().any(|x| x.is_alive() && key(x))
     }
 
     fn all<F>(&self, key: F) -> bool
     where
         F: Fn(&AgentRef<A>) -> bool,
     {
-        self.values().all(|x| key(x))
+        self.values().all(|x| !x.is_alive() || key(x))
     }
 
-    fn min_one_of<T, F>(&self, mut key: F) -> AgentRef<A>
+    fn min_one_of<T, F>(&self, mut key: F) -> Option<AgentRef<A>>
     where
         T: Ord,
         F: FnMut(&AgentRef<A>) -> T,
     {
         self.values()
+            .filter(|agent| agent.is_alive())
             .min_by_key(|&agent| key(agent))
-            .unwrap()
-            .clone()
+            .cloned()
     }
 
-    fn max_one_of<T, F>(&self, mut key: F) -> AgentRef<A>
+    fn max_one_of<T, F>(&self, mut key: F) -> Option<AgentRef<A>>
     where
         T: Ord,
         F: FnMut(&AgentRef<A>) -> T,
     {
         self.values()
+            .filter(|agent| agent.is_alive())
             .max_by_key(|&agent| key(agent))
-            .unwrap()
-            .clone()
+            .cloned()
     }
 
     fn min_n_of<T, F>(&self, amount: usize, mut key: F) -> Self
@@ -74,7 +89,7 @@ where
         T: Ord,
         F: FnMut(&AgentRef<A>) -> T,
     {
-        let mut agents = self.values().collect_vec();
+        let mut agents = self.values().filter(|agent| agent.is_alive()).collect_vec();
         agents.sort_by_cached_key(|&x| key(x));
         agents
             .iter()
@@ -91,7 +106,7 @@ where
         T: Ord,
         F: FnMut(&AgentRef<A>) -> T,
     {
-        let mut agents = self.values().collect_vec();
+        let mut agents = self.values().filter(|agent| agent.is_alive()).collect_vec();
         agents.sort_by_cached_key(|&x| key(x));
         agents
             .iter()
@@ -104,14 +119,20 @@ where
             .collect()
     }
 
-    fn one_of(&self) -> AgentRef<A> {
-        let rng = self.get_rng();
-        let idx = rng.usize(0..self.len());
-        self.values().nth(idx).unwrap().clone()
+    fn one_of(&self) -> Option<AgentRef<A>> {
+        self.try_one_of().ok()
+    }
+
+    fn try_one_of(&self) -> Result<AgentRef<A>, RuscapeError> {
+        let live = self.live();
+        if live.is_empty() {
+            return Err(RuscapeError::EmptyAgentSet);
+        }
+        Ok(self[live[self.get_rng().usize(0..live.len())]].clone())
     }
 
     #[inline]
-    fn one_of_weighted_by<K, F>(&self, key: F) -> AgentRef<A>
+    fn one_of_weighted_by<K, F>(&self, mut key: F) -> Option<AgentRef<A>>
     where
         K: Ord
             + Clone
@@ -120,18 +141,28 @@ where
             + for<'a> std::ops::AddAssign<&'a K>,
         F: FnMut(&AgentRef<A>) -> K,
     {
-        self.n_of_weighted_by(1, key)[0].clone()
+        let live = self.live();
+        let weights =
+            rand::distributions::WeightedIndex::new(live.iter().map(|&idx| key(&self[idx])))
+                .ok()?;
+        let rng = self.get_rng();
+        Some(self[live[RandRng(&rng).sample(&weights)]].clone())
     }
 
     #[inline]
     fn n_of(&self, amount: usize) -> Self {
+        self.try_n_of(amount).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn try_n_of(&self, amount: usize) -> Result<Self, RuscapeError> {
         let mut agents = self.clone();
+        agents.retain(|_, agent| agent.is_alive());
         (0..amount)
             .map(|_| {
-                let agent = agents.one_of();
+                let agent = agents.try_one_of()?;
                 agents.delete(&agent);
                 let who = agent.borrow().who();
-                (who, agent)
+                Ok((who, agent))
             })
             .collect()
     }
@@ -144,7 +175,8 @@ where
         self.n_of_weighted_by(amount, |_| 1)
     }
 
-    fn n_of_weighted_by<K, F>(&self, amount: usize, mut key: F) -> Self
+    // empty when no live member has a positive weight, like one_of_weighted_by
+    fn n_of_weighted_by<K, F>(&self, amount: usize, key: F) -> Self
     where
         K: Ord
             + Clone
@@ -153,50 +185,200 @@ where
             + for<'a> std::ops::AddAssign<&'a K>,
         F: FnMut(&AgentRef<A>) -> K,
     {
-        let keys = (0..self.len()).map(|idx| key(&self[idx]));
-        let weights = rand::distributions::WeightedIndex::new(keys).unwrap();
-        (0..amount)
+        self.try_n_of_weighted_by(amount, key).unwrap_or_else(|_| {
+            let mut agents = self.clone();
+            agents.clear();
+            agents
+        })
+    }
+
+    fn try_n_of_weighted_by<K, F>(&self, amount: usize, mut key: F) -> Result<Self, RuscapeError>
+    where
+        K: Ord
+            + Clone
+            + Default
+            + rand_distr::uniform::SampleUniform
+            + for<'a> std::ops::AddAssign<&'a K>,
+        F: FnMut(&AgentRef<A>) -> K,
+    {
+        if amount == 0 {
+            let mut agents = self.clone();
+            agents.clear();
+            return Ok(agents);
+        }
+        let live = self.live();
+        let weights =
+            rand::distributions::WeightedIndex::new(live.iter().map(|&idx| key(&self[idx])))
+                .map_err(|_| RuscapeError::EmptyAgentSet)?;
+        let rng = self.get_rng();
+        let mut rng = RandRng(&rng);
+        Ok((0..amount)
             .map(|_| {
-                let agent = self[thread_rng().sample(&weights)].clone();
+                let agent = self[live[rng.sample(&weights)]].clone();
                 let who = agent.borrow().who();
                 (who, agent)
             })
-            .collect()
+            .collect())
     }
 
-    fn ask<F: FnMut(&AgentRef<A>)>(&self, mut f: F) {
-        let mut keys = (0..self.len()).collect_vec();
-        let rng = self.get_rng();
-        rng.shuffle(&mut keys);
-        for &key in keys.iter() {
-            f(&self[key]);
+    fn effective_activation(&self) -> Activation {
+        match self.activation() {
+            Some(activation) => activation,
+            None if self.len() == 0 => Default::default(),
+            None => self.get_world().borrow().activation(),
         }
     }
 
+    fn activation_order(&self, activation: Activation) -> Vec<usize> {
+        let mut live = self.live();
+        if live.is_empty() {
+            return live;
+        }
+        match activation {
+            Activation::Sequential => live,
+            Activation::Random => {
+                self.get_rng().shuffle(&mut live);
+                live
+            }
+            Activation::RandomWithReplacement => {
+                let rng = self.get_rng();
+                (0..live.len())
+                    .map(|_| live[rng.usize(0..live.len())])
+                    .collect()
+            }
+            Activation::SortedBy(key) => {
+                let mut keyed = live
+                    .into_iter()
+                    .map(|idx| (self[idx].try_get_as::<f64>(key).ok(), idx))
+                    .collect_vec();
+                keyed.sort_by(|(a, _), (b, _)| match (a, b) {
+                    (Some(a), Some(b)) => a.total_cmp(b),
+                    _ => b.is_some().cmp(&a.is_some()),
+                });
+                keyed.into_iter().map(|(_, idx)| idx).collect()
+            }
+        }
+    }
+
+    fn ask<F: FnMut(&AgentRef<A>)>(&self, f: F) {
+        self.ask_with(self.effective_activation(), f)
+    }
+
+    fn ask_with<F: FnMut(&AgentRef<A>)>(&self, activation: Activation, mut f: F) {
+        for key in self.activation_order(activation) {
+            if self[key].is_alive() {
+                f(&self[key]);
+            }
+        }
+    }
+
+    fn ask_sorted_by<T, K, F>(&self, mut key: K, mut f: F)
+    where
+        T: Ord,
+        K: FnMut(&AgentRef<A>) -> T,
+        F: FnMut(&AgentRef<A>),
+    {
+        let mut agents = self.values().filter(|agent| agent.is_alive()).collect_vec();
+        agents.sort_by_cached_key(|&x| key(x));
+        agents.into_iter().for_each(|agent| {
+            if agent.is_alive() {
+                f(agent)
+            }
+        });
+    }
+
+    fn ask_staged(&self, stages: &mut [&mut dyn FnMut(&AgentRef<A>)]) {
+        let order = self.activation_order(self.effective_activation());
+        for stage in stages.iter_mut() {
+            for &key in order.iter() {
+                if self[key].is_alive() {
+                    stage(&self[key]);
+                }
+            }
+        }
+    }
+
+    fn ask_sync<F: FnMut(&AgentRef<A>)>(&self, f: F) {
+        let live = self.live().into_iter().map(|idx| &self[idx]).collect_vec();
+        live.iter()
+            .for_each(|agent| agent.borrow_mut().begin_sync());
+        let _sync = SyncGuard(live);
+        self.ask(f);
+    }
+
+    fn ask_deferred<F: FnMut(&AgentRef<A>, &mut CommandBuffer)>(&self, mut f: F) {
+        let mut commands = CommandBuffer::new();
+        self.ask(|agent| f(agent, &mut commands));
+        commands.apply();
+    }
+
+    fn try_ask_deferred<F: FnMut(&AgentRef<A>, &mut CommandBuffer)>(
+        &self,
+        mut f: F,
+    ) -> Result<(), RuscapeError> {
+        let mut commands = CommandBuffer::new();
+        self.ask(|agent| f(agent, &mut commands));
+        commands.try_apply()
+    }
+
     fn ask_each<F: FnMut(&AgentRef<A>)>(&self, mut f: F) {
-        self.values().for_each(|agent| f(agent));
+        self.values()
+            .filter(|agent| agent.is_alive())
+            .for_each(|agent| f(agent));
     }
 
     fn report<T, F: FnMut(&AgentRef<A>) -> T>(&self, mut f: F) -> Vec<(A::IDX, T)> {
-        self.iter().map(|(&key, agent)| (key, f(agent))).collect()
+        self.iter()
+            .filter(|(_, agent)| agent.is_alive())
+            .map(|(&key, agent)| (key, f(agent)))
+            .collect()
     }
 
-    fn own(&mut self, owns: Vec<&'static str>) {
-        owns.iter().for_each(|&own| {
-            self.ask(|p| {
-                p.borrow_mut().insert(own, Default::default());
+    fn own<I>(&mut self, owns: I)
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let live = self.live();
+        owns.into_iter().for_each(|own| {
+            let own = VarKey::new(own.as_ref());
+            live.iter().for_each(|&idx| {
+                self[idx]
+                    .borrow_mut()
+                    .insert(own.clone(), Default::default());
             })
         })
     }
 
+    fn own_typed<T: Into<Variable>>(
+        &mut self,
+        key: &str,
+        var_type: VarType,
+        default: T,
+    ) -> Result<(), RuscapeError> {
+        let key = VarKey::new(key);
+        let default = default.into();
+        self.live().into_iter().try_for_each(|idx| {
+            self[idx]
+                .borrow_mut()
+                .own_typed(key.clone(), var_type, default.clone())
+        })
+    }
+
+    fn own_vars<V: AgentVars, F: FnMut(&AgentRef<A>) -> V>(&mut self, mut init: F) {
+        self.values()
+            .filter(|agent| agent.is_alive())
+            .for_each(|agent| agent.attach_vars(init(agent)));
+    }
+
     fn index(&self, idx: A::IDX) -> AgentRef<A> {
         self.get(&idx).unwrap().clone()
     }
 
-    fn with<F: FnMut(&AgentRef<A>) -> bool>(&self, f: F) -> Self {
+    fn with<F: FnMut(&AgentRef<A>) -> bool>(&self, mut f: F) -> Self {
         self.values()
+            .filter(|&agent| agent.is_alive() && f(agent))
             .cloned()
-            .filter(f)
             .map(|agent| {
                 let who = agent.borrow().who();
                 (who, agent)
@@ -218,8 +400,10 @@ where
     }
 
     fn extends(&mut self, agents: &Self) {
-        if self.len() == 0 {
-            self.set_world(&WorldRef::new(&agents.get_world()));
+        if self.try_get_world().is_err() {
+            if let Ok(w) = agents.try_get_world() {
+                self.set_world(&WorldRef::new(&w));
+            }
         }
         self.extend(agents.iter().map(|(&k, v)| (k, v.clone())));
     }
@@ -232,13 +416,35 @@ where
 
 pub trait HasWorld {
     fn get_world(&self) -> Rc<RefCell<World>>;
+    fn try_get_world(&self) -> Result<Rc<RefCell<World>>, RuscapeError>;
     fn set_world(&mut self, w: &WorldRef);
 }
 
+// ends an ask_sync even if the closure panics, dropping the buffered writes in that case
+struct SyncGuard<'a, A: Agent>(Vec<&'a AgentRef<A>>);
+
+impl<A: Agent> Drop for SyncGuard<'_, A> {
+    fn drop(&mut self) {
+        let panicking = std::thread::panicking();
+        self.0.iter().for_each(|agent| {
+            if let Ok(mut agent) = agent.try_borrow_mut() {
+                if panicking {
+                    agent.abort_sync();
+                } else {
+                    agent.commit_sync();
+                }
+            }
+        });
+    }
+}
+
 impl HasWorld for TurtleSet {
     fn get_world(&self) -> Rc<RefCell<World>> {
         self.world()
     }
+    fn try_get_world(&self) -> Result<Rc<RefCell<World>>, RuscapeError> {
+        self.try_world()
+    }
     fn set_world(&mut self, w: &WorldRef) {
         self.set_w(Some(w.clone()))
     }
@@ -248,6 +454,9 @@ impl HasWorld for PatchSet {
     fn get_world(&self) -> Rc<RefCell<World>> {
         self.world()
     }
+    fn try_get_world(&self) -> Result<Rc<RefCell<World>>, RuscapeError> {
+        self.try_world()
+    }
     fn set_world(&mut self, w: &WorldRef) {
         self.set_w(Some(w.clone()))
     }
@@ -256,6 +465,9 @@ impl HasWorld for LinkSet {
     fn get_world(&self) -> Rc<RefCell<World>> {
         self.world()
     }
+    fn try_get_world(&self) -> Result<Rc<RefCell<World>>, RuscapeError> {
+        self.try_world()
+    }
     fn set_world(&mut self, w: &WorldRef) {
         self.set_w(Some(w.clone()))
     }
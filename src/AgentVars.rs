@@ -0,0 +1,71 @@
+use crate::Agent::{Agent, AgentRef};
+use crate::Error::RuscapeError;
+use crate::Variable::Variable;
+use std::any::{type_name, Any};
+use std::cell::{Ref, RefMut};
+use std::convert::TryFrom;
+
+pub trait AgentVars: Any {
+    fn var_names(&self) -> &'static [&'static str];
+    fn get_var(&self, key: &str) -> Option<Variable>;
+    fn set_var(&mut self, key: &str, value: Variable) -> Option<Result<(), RuscapeError>>;
+    fn clone_vars(&self) -> Box<dyn AgentVars>;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+pub fn convert_var<T>(value: Variable) -> Result<T, RuscapeError>
+where
+    T: TryFrom<Variable>,
+    RuscapeError: From<T::Error>,
+{
+    Ok(T::try_from(value)?)
+}
+
+pub fn var_from<T>(value: T) -> Result<Variable, RuscapeError>
+where
+    Variable: TryFrom<T>,
+    RuscapeError: From<<Variable as TryFrom<T>>::Error>,
+{
+    Ok(Variable::try_from(value)?)
+}
+
+impl<A: Agent> AgentRef<A> {
+    pub fn try_vars<V: AgentVars>(&self) -> Result<Ref<'_, V>, RuscapeError> {
+        let agent = self.borrow();
+        if agent
+            .vars()
+            .and_then(|vars| vars.as_any().downcast_ref::<V>())
+            .is_none()
+        {
+            return Err(RuscapeError::MissingVars {
+                agent: format!("{:?}", agent.who()),
+                vars: type_name::<V>(),
+            });
+        }
+        Ok(Ref::map(agent, |agent| {
+            agent.vars().unwrap().as_any().downcast_ref().unwrap()
+        }))
+    }
+    pub fn try_vars_mut<V: AgentVars>(&self) -> Result<RefMut<'_, V>, RuscapeError> {
+        self.try_vars::<V>()?;
+        Ok(RefMut::map(self.borrow_mut(), |agent| {
+            agent
+                .vars_mut()
+                .as_mut()
+                .unwrap()
+                .as_any_mut()
+                .downcast_mut()
+                .unwrap()
+        }))
+    }
+    pub fn vars<V: AgentVars>(&self) -> Ref<'_, V> {
+        self.try_vars().unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn vars_mut<V: AgentVars>(&self) -> RefMut<'_, V> {
+        self.try_vars_mut().unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn attach_vars<V: AgentVars>(&self, vars: V) {
+        *self.borrow_mut().vars_mut() = Some(Box::new(vars));
+    }
+}
@@ -0,0 +1,306 @@
+use crate::AgentSet::AgentSet;
+use crate::Error::RuscapeError;
+use crate::LinkSet::LinkSet;
+use crate::MapType::HashMap;
+use crate::PatchRef::PatchRef;
+use crate::TurtleRef::TurtleRef;
+use crate::TurtleSet::TurtleSet;
+use crate::VarKey::VarKey;
+use crate::World::{World, WorldRef};
+use itertools::Itertools;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+
+#[derive(Default)]
+pub(crate) struct Breeds {
+    turtles: HashMap<String, (TurtleSet, Vec<VarKey>)>,
+    links: HashMap<String, bool>,
+}
+
+impl World {
+    pub fn declare_breed<I>(&mut self, name: &str, owns: I)
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let owns = owns
+            .into_iter()
+            .map(|own| VarKey::new(own.as_ref()))
+            .collect_vec();
+        // declaring a breed again keeps its members and moves them to the new variables
+        if let Some((turtles, old)) = self.breeds.turtles.get_mut(name) {
+            turtles.values().for_each(|t| {
+                let mut t = t.borrow_mut();
+                old.iter()
+                    .filter(|own| !owns.contains(own))
+                    .for_each(|own| {
+                        t.remove(own.as_str());
+                    });
+                owns.iter().for_each(|own| {
+                    t.entry(own.clone()).or_default();
+                });
+            });
+            *old = owns;
+            return;
+        }
+        let mut turtles = TurtleSet::default();
+        turtles.set_w(Some(WorldRef::new(&self.turtles.world())));
+        self.breeds
+            .turtles
+            .insert(name.to_string(), (turtles, owns));
+    }
+    pub fn declare_link_breed(&mut self, name: &str, directed: bool) {
+        self.breeds.links.insert(name.to_string(), directed);
+    }
+    pub fn breed(&self, name: &str) -> TurtleSet {
+        self.try_breed(name).unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_breed(&self, name: &str) -> Result<TurtleSet, RuscapeError> {
+        self.breeds
+            .turtles
+            .get(name)
+            .map(|(turtles, _)| turtles.clone())
+            .ok_or_else(|| RuscapeError::UnknownBreed(name.to_string()))
+    }
+    pub fn create_breed(&mut self, name: &str, amount: usize) -> Result<TurtleSet, RuscapeError> {
+        self.try_breed(name)?;
+        let turtles = self.crt(amount);
+        turtles
+            .values()
+            .try_for_each(|t| self.assign_breed(t, Some(name)))?;
+        Ok(turtles)
+    }
+    pub fn link_breed(&self, name: &str) -> LinkSet {
+        let directed = self
+            .link_breed_direction(name)
+            .unwrap_or_else(|e| panic!("{}", e));
+        let links = self
+            .all_links()
+            .filter(|link| link.borrow().is_directed() == directed)
+            .cloned()
+            .collect_vec();
+        let mut links: LinkSet = links
+            .into_iter()
+            .filter(|link| link.borrow().breed() == Some(name))
+            .collect_vec()
+            .into();
+        if links.w().is_none() {
+            links.set_w(Some(WorldRef::new(&self.turtles.world())));
+        }
+        links
+    }
+
+    pub(crate) fn link_breed_direction(&self, name: &str) -> Result<bool, RuscapeError> {
+        self.breeds
+            .links
+            .get(name)
+            .cloned()
+            .ok_or_else(|| RuscapeError::UnknownBreed(name.to_string()))
+    }
+    // link breeds are never removed, so their declaration order is a stable id
+    pub(crate) fn link_breed_id(&self, name: &str) -> usize {
+        self.breeds
+            .links
+            .get_index_of(name)
+            .unwrap_or_else(|| panic!("{}", RuscapeError::UnknownBreed(name.to_string())))
+    }
+    pub(crate) fn register_breed(&mut self, t: &TurtleRef) {
+        let breed = t.borrow().breed().map(String::from);
+        if let Some((turtles, _)) = breed.and_then(|breed| self.breeds.turtles.get_mut(&breed)) {
+            turtles.append(t);
+        }
+    }
+    pub(crate) fn unregister_breed(&mut self, t: &TurtleRef) {
+        let breed = t.borrow().breed().map(String::from);
+        if let Some((turtles, _)) = breed.and_then(|breed| self.breeds.turtles.get_mut(&breed)) {
+            turtles.delete(t);
+        }
+    }
+    pub(crate) fn assign_breed(
+        &mut self,
+        t: &TurtleRef,
+        breed: Option<&str>,
+    ) -> Result<(), RuscapeError> {
+        let breed = breed.filter(|&breed| breed != "turtles");
+        if let Some(breed) = breed {
+            self.try_breed(breed)?;
+        }
+        self.unregister_breed(t);
+        let old = t.borrow().breed().map(String::from);
+        if let Some((_, owns)) = old.and_then(|old| self.breeds.turtles.get(&old)) {
+            owns.iter().for_each(|own| {
+                t.borrow_mut().remove(own.as_str());
+            });
+        }
+        t.borrow_mut().set_breed(breed.map(String::from));
+        if let Some((_, owns)) = breed.and_then(|breed| self.breeds.turtles.get(breed)) {
+            owns.iter().for_each(|own| {
+                t.borrow_mut().entry(own.clone()).or_default();
+            });
+        }
+        self.register_breed(t);
+        Ok(())
+    }
+}
+
+impl TurtleRef {
+    #[inline]
+    pub fn breed(&self) -> Option<String> {
+        self.borrow().breed().map(String::from)
+    }
+    pub fn set_breed(&self, breed: &str) -> Result<(), RuscapeError> {
+        self.check_alive()?;
+        self.world().borrow_mut().assign_breed(self, Some(breed))
+    }
+    pub fn hatch_breed(&self, breed: &str, amount: usize) -> Result<TurtleSet, RuscapeError> {
+        self.world().borrow().try_breed(breed)?;
+        let turtles = self.hatch(amount);
+        turtles
+            .values()
+            .try_for_each(|t| self.world().borrow_mut().assign_breed(t, Some(breed)))?;
+        Ok(turtles)
+    }
+    pub fn breed_here(&self, breed: &str) -> TurtleSet {
+        self.patch_here().breed_on(breed)
+    }
+}
+
+impl PatchRef {
+    pub fn breed_on(&self, breed: &str) -> TurtleSet {
+        self.turtles_on()
+            .with(|t| t.borrow().breed() == Some(breed))
+    }
+}
+
+impl TurtleRef {
+    pub fn create_breed_link_with(
+        &self,
+        breed: &str,
+        other: &TurtleRef,
+    ) -> Result<(), RuscapeError> {
+        self.create_breed_link(breed, false, self, other)
+    }
+    pub fn create_breed_link_to(&self, breed: &str, other: &TurtleRef) -> Result<(), RuscapeError> {
+        self.create_breed_link(breed, true, self, other)
+    }
+    pub fn create_breed_link_from(
+        &self,
+        breed: &str,
+        other: &TurtleRef,
+    ) -> Result<(), RuscapeError> {
+        self.create_breed_link(breed, true, other, self)
+    }
+    fn create_breed_link(
+        &self,
+        breed: &str,
+        directed: bool,
+        end1: &TurtleRef,
+        end2: &TurtleRef,
+    ) -> Result<(), RuscapeError> {
+        end1.check_alive()?;
+        end2.check_alive()?;
+        let world = self.world();
+        if world.borrow().link_breed_direction(breed)? != directed {
+            return Err(RuscapeError::LinkBreedDirection(breed.to_string()));
+        }
+        let (nid1, nid2) = (end1.borrow().nid(), end2.borrow().nid());
+        world
+            .borrow_mut()
+            .add_link(nid1, nid2, directed, Some(breed))?;
+        Ok(())
+    }
+    pub fn my_breed_links(&self, breed: &str) -> LinkSet {
+        self.assert_alive();
+        let world = self.world();
+        let world = world.borrow();
+        let nid = self.borrow().nid();
+        let links = if world
+            .link_breed_direction(breed)
+            .unwrap_or_else(|e| panic!("{}", e))
+        {
+            world
+                .dg
+                .edges_directed(nid, Direction::Outgoing)
+                .chain(world.dg.edges_directed(nid, Direction::Incoming))
+                .map(|e| e.weight().clone())
+                .collect_vec()
+        } else {
+            world
+                .ug
+                .edges(nid)
+                .map(|e| e.weight().clone())
+                .collect_vec()
+        };
+        links
+            .into_iter()
+            .filter(|link| link.borrow().breed() == Some(breed))
+            .collect_vec()
+            .into()
+    }
+    #[inline]
+    pub fn breed_link_neighbors(&self, breed: &str) -> TurtleSet {
+        self.try_breed_link_neighbors(breed)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_breed_link_neighbors(&self, breed: &str) -> Result<TurtleSet, RuscapeError> {
+        self.breed_neighbors(breed, None)
+    }
+    #[inline]
+    pub fn out_breed_link_neighbors(&self, breed: &str) -> TurtleSet {
+        self.try_out_breed_link_neighbors(breed)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_out_breed_link_neighbors(&self, breed: &str) -> Result<TurtleSet, RuscapeError> {
+        self.breed_neighbors(breed, Some(Direction::Outgoing))
+    }
+    #[inline]
+    pub fn in_breed_link_neighbors(&self, breed: &str) -> TurtleSet {
+        self.try_in_breed_link_neighbors(breed)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_in_breed_link_neighbors(&self, breed: &str) -> Result<TurtleSet, RuscapeError> {
+        self.breed_neighbors(breed, Some(Direction::Incoming))
+    }
+    // the undirected form is for undirected breeds, the in and out forms for directed ones
+    fn breed_neighbors(
+        &self,
+        breed: &str,
+        direction: Option<Direction>,
+    ) -> Result<TurtleSet, RuscapeError> {
+        self.check_alive()?;
+        let world = self.world();
+        let world = world.borrow();
+        if world.link_breed_direction(breed)? != direction.is_some() {
+            return Err(RuscapeError::LinkBreedDirection(breed.to_string()));
+        }
+        let nid = self.borrow().nid();
+        let others = match direction {
+            Some(direction) => world
+                .dg
+                .edges_directed(nid, direction)
+                .filter(|e| e.weight().borrow().breed() == Some(breed))
+                .map(|e| match direction {
+                    Direction::Outgoing => e.target(),
+                    Direction::Incoming => e.source(),
+                })
+                .collect_vec(),
+            None => world
+                .ug
+                .edges(nid)
+                .filter(|e| e.weight().borrow().breed() == Some(breed))
+                .map(|e| {
+                    if e.source() == nid {
+                        e.target()
+                    } else {
+                        e.source()
+                    }
+                })
+                .collect_vec(),
+        };
+        Ok(others
+            .into_iter()
+            .map(|other| world.ug[other].clone())
+            .collect_vec()
+            .into())
+    }
+}
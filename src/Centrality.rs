@@ -0,0 +1,260 @@
+use crate::Error::RuscapeError;
+use crate::MapType::HashMap;
+use crate::World::World;
+use itertools::Itertools;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Centrality {
+    Degree,
+    Betweenness,
+    Closeness,
+    Eigenvector,
+    PageRank { damping: f64 },
+}
+
+// live turtles indexed in world order; undirected links become arcs both ways
+struct Network {
+    whos: Vec<usize>,
+    links: Vec<(usize, usize, f64)>,
+    arcs: Vec<Vec<(usize, f64)>>,
+}
+
+impl Network {
+    // lengths feed the shortest paths, so they have to be strictly positive
+    fn new(world: &World, weight: Option<&str>, lengths: bool) -> Result<Self, RuscapeError> {
+        let whos = world.turtles.values().map(|t| t.who()).collect_vec();
+        let index: HashMap<usize, usize> = whos.iter().enumerate().map(|(i, &w)| (w, i)).collect();
+        let mut arcs = vec![vec![]; whos.len()];
+        let links = world
+            .all_links()
+            .map(|link| {
+                let w = match weight {
+                    Some(key) => link.try_get_as::<f64>(key)?,
+                    None => 1.,
+                };
+                if !w.is_finite() || w < 0. {
+                    return Err(RuscapeError::InvalidNetwork(
+                        "link weights must be finite and not negative".to_string(),
+                    ));
+                }
+                if lengths && w == 0. {
+                    return Err(RuscapeError::InvalidNetwork(
+                        "link lengths must be positive".to_string(),
+                    ));
+                }
+                let (end1, end2) = link.who();
+                let (a, b) = (index[&end1], index[&end2]);
+                arcs[a].push((b, w));
+                if !link.is_directed() {
+                    arcs[b].push((a, w));
+                }
+                Ok((a, b, w))
+            })
+            .collect::<Result<Vec<_>, RuscapeError>>()?;
+        Ok(Network { whos, links, arcs })
+    }
+
+    fn len(&self) -> usize {
+        self.whos.len()
+    }
+
+    fn report(&self, values: Vec<f64>) -> Vec<(usize, f64)> {
+        self.whos.iter().cloned().zip(values).collect()
+    }
+
+    fn degree(&self) -> Vec<f64> {
+        let n = self.len();
+        let scale = if n > 1 { 1. / (n - 1) as f64 } else { 1. };
+        let mut degree = vec![0.; n];
+        self.links.iter().for_each(|&(a, b, w)| {
+            degree[a] += w;
+            degree[b] += w;
+        });
+        degree.iter().map(|d| d * scale).collect()
+    }
+
+    // distances, path counts, predecessors and settling order from `source`
+    fn shortest_paths(&self, source: usize) -> (Vec<f64>, Vec<f64>, Vec<Vec<usize>>, Vec<usize>) {
+        let n = self.len();
+        let mut dist = vec![f64::INFINITY; n];
+        let mut sigma = vec![0.; n];
+        let mut preds = vec![vec![]; n];
+        let mut done = vec![false; n];
+        let mut order = vec![];
+        let mut queue = BinaryHeap::new();
+        dist[source] = 0.;
+        sigma[source] = 1.;
+        queue.push(Visit(0., source));
+        while let Some(Visit(d, v)) = queue.pop() {
+            if done[v] {
+                continue;
+            }
+            done[v] = true;
+            order.push(v);
+            for &(u, w) in &self.arcs[v] {
+                let next = d + w;
+                if done[u] {
+                    continue;
+                }
+                if next < dist[u] {
+                    dist[u] = next;
+                    sigma[u] = sigma[v];
+                    preds[u] = vec![v];
+                    queue.push(Visit(next, u));
+                } else if next == dist[u] {
+                    sigma[u] += sigma[v];
+                    preds[u].push(v);
+                }
+            }
+        }
+        (dist, sigma, preds, order)
+    }
+
+    fn betweenness(&self) -> Vec<f64> {
+        let n = self.len();
+        let mut betweenness = vec![0.; n];
+        for source in 0..n {
+            let (_, sigma, preds, order) = self.shortest_paths(source);
+            let mut delta = vec![0.; n];
+            for &u in order.iter().rev() {
+                for &v in &preds[u] {
+                    delta[v] += sigma[v] / sigma[u] * (1. + delta[u]);
+                }
+                if u != source {
+                    betweenness[u] += delta[u];
+                }
+            }
+        }
+        // normalised over the ordered pairs of the other turtles
+        let scale = if n > 2 {
+            1. / ((n - 1) * (n - 2)) as f64
+        } else {
+            0.
+        };
+        betweenness.iter().map(|b| b * scale).collect()
+    }
+
+    fn closeness(&self) -> Vec<f64> {
+        let n = self.len();
+        (0..n)
+            .map(|source| {
+                let (dist, _, _, order) = self.shortest_paths(source);
+                let reached = order.len() - 1;
+                let total: f64 = order.iter().map(|&v| dist[v]).sum();
+                if reached == 0 || total == 0. {
+                    0.
+                } else {
+                    reached as f64 / total * reached as f64 / (n - 1) as f64
+                }
+            })
+            .collect()
+    }
+
+    fn eigenvector(&self) -> Vec<f64> {
+        let n = self.len();
+        let mut x = vec![1. / n as f64; n];
+        for _ in 0..1000 {
+            let mut next = x.clone();
+            (0..n).for_each(|v| self.arcs[v].iter().for_each(|&(u, w)| next[u] += x[v] * w));
+            let norm = next.iter().map(|v| v * v).sum::<f64>().sqrt();
+            if norm == 0. {
+                return next;
+            }
+            next.iter_mut().for_each(|v| *v /= norm);
+            let change: f64 = next.iter().zip(&x).map(|(a, b)| (a - b).abs()).sum();
+            x = next;
+            if change < n as f64 * 1e-9 {
+                break;
+            }
+        }
+        x
+    }
+
+    fn page_rank(&self, damping: f64) -> Vec<f64> {
+        let n = self.len();
+        let out = self
+            .arcs
+            .iter()
+            .map(|arcs| arcs.iter().map(|&(_, w)| w).sum::<f64>())
+            .collect_vec();
+        let mut x = vec![1. / n as f64; n];
+        for _ in 0..1000 {
+            let dangling: f64 = (0..n).filter(|&v| out[v] == 0.).map(|v| x[v]).sum();
+            let base = (1. - damping + damping * dangling) / n as f64;
+            let mut next = vec![base; n];
+            (0..n).filter(|&v| out[v] > 0.).for_each(|v| {
+                self.arcs[v]
+                    .iter()
+                    .for_each(|&(u, w)| next[u] += damping * x[v] * w / out[v])
+            });
+            let change: f64 = next.iter().zip(&x).map(|(a, b)| (a - b).abs()).sum();
+            x = next;
+            if change < n as f64 * 1e-10 {
+                break;
+            }
+        }
+        x
+    }
+}
+
+#[derive(PartialEq)]
+struct Visit(f64, usize);
+
+impl Eq for Visit {}
+
+impl PartialOrd for Visit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// reversed so that the binary heap pops the closest turtle first
+impl Ord for Visit {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .0
+            .partial_cmp(&self.0)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.1.cmp(&self.1))
+    }
+}
+
+impl World {
+    // weights are link lengths for betweenness and closeness, link strengths otherwise
+    pub fn centrality(
+        &self,
+        kind: Centrality,
+        weight: Option<&str>,
+    ) -> Result<Vec<(usize, f64)>, RuscapeError> {
+        if let Centrality::PageRank { damping } = kind {
+            if !(0. ..=1.).contains(&damping) {
+                return Err(RuscapeError::InvalidNetwork(
+                    "the damping factor must be between 0 and 1".to_string(),
+                ));
+            }
+        }
+        let lengths = matches!(kind, Centrality::Betweenness | Centrality::Closeness);
+        let network = Network::new(self, weight, lengths)?;
+        let values = match kind {
+            Centrality::Degree => network.degree(),
+            Centrality::Betweenness => network.betweenness(),
+            Centrality::Closeness => network.closeness(),
+            Centrality::Eigenvector => network.eigenvector(),
+            Centrality::PageRank { damping } => network.page_rank(damping),
+        };
+        Ok(network.report(values))
+    }
+
+    pub fn set_centrality(
+        &self,
+        key: &str,
+        kind: Centrality,
+        weight: Option<&str>,
+    ) -> Result<(), RuscapeError> {
+        self.centrality(kind, weight)?
+            .into_iter()
+            .try_for_each(|(who, value)| self.turtles[&who].try_set(key, value))
+    }
+}
@@ -0,0 +1,120 @@
+use crate::Error::RuscapeError;
+use crate::PatchRef::PatchRef;
+use crate::TurtleRef::TurtleRef;
+use crate::TurtleSet::TurtleSet;
+
+enum Command {
+    Die(TurtleRef),
+    Hatch(TurtleRef, usize, Box<dyn FnOnce(&TurtleSet)>),
+    Sprout(PatchRef, usize, Box<dyn FnOnce(&TurtleSet)>),
+    CreateLinkWith(TurtleRef, TurtleRef),
+    CreateLinkTo(TurtleRef, TurtleRef),
+    Setxy(TurtleRef, f64, f64),
+    Run(Box<dyn FnOnce()>),
+}
+
+#[derive(Default)]
+pub struct CommandBuffer {
+    commands: Vec<Command>,
+}
+
+impl CommandBuffer {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    pub fn die(&mut self, turtle: &TurtleRef) {
+        self.commands.push(Command::Die(turtle.clone()));
+    }
+
+    pub fn hatch<F: FnOnce(&TurtleSet) + 'static>(
+        &mut self,
+        turtle: &TurtleRef,
+        amount: usize,
+        then: F,
+    ) {
+        self.commands
+            .push(Command::Hatch(turtle.clone(), amount, Box::new(then)));
+    }
+
+    pub fn sprout<F: FnOnce(&TurtleSet) + 'static>(
+        &mut self,
+        patch: &PatchRef,
+        amount: usize,
+        then: F,
+    ) {
+        self.commands
+            .push(Command::Sprout(patch.clone(), amount, Box::new(then)));
+    }
+
+    pub fn create_link_with(&mut self, turtle: &TurtleRef, other: &TurtleRef) {
+        self.commands
+            .push(Command::CreateLinkWith(turtle.clone(), other.clone()));
+    }
+
+    pub fn create_link_to(&mut self, turtle: &TurtleRef, other: &TurtleRef) {
+        self.commands
+            .push(Command::CreateLinkTo(turtle.clone(), other.clone()));
+    }
+
+    pub fn setxy(&mut self, turtle: &TurtleRef, x: f64, y: f64) {
+        self.commands.push(Command::Setxy(turtle.clone(), x, y));
+    }
+
+    pub fn run<F: FnOnce() + 'static>(&mut self, f: F) {
+        self.commands.push(Command::Run(Box::new(f)));
+    }
+
+    #[inline]
+    pub fn apply(&mut self) {
+        self.try_apply().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // commands on turtles that died earlier in the buffer, and links that clash with an
+    // existing link, are skipped; any other error stops the buffer and drops what is left
+    pub fn try_apply(&mut self) -> Result<(), RuscapeError> {
+        for command in self.commands.drain(..) {
+            match command {
+                Command::Die(turtle) => turtle.die(),
+                Command::Hatch(turtle, amount, then) => {
+                    if turtle.is_alive() {
+                        then(&turtle.hatch(amount))
+                    }
+                }
+                Command::Sprout(patch, amount, then) => then(&patch.sprout(amount)),
+                Command::CreateLinkWith(turtle, other) => {
+                    if turtle.is_alive() && other.is_alive() {
+                        skip_existing(turtle.try_create_link_with(&other))?
+                    }
+                }
+                Command::CreateLinkTo(turtle, other) => {
+                    if turtle.is_alive() && other.is_alive() {
+                        skip_existing(turtle.try_create_link_to(&other))?
+                    }
+                }
+                Command::Setxy(turtle, x, y) => {
+                    if turtle.is_alive() {
+                        turtle.setxy(x, y);
+                    }
+                }
+                Command::Run(f) => f(),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn skip_existing(created: Result<(), RuscapeError>) -> Result<(), RuscapeError> {
+    match created {
+        Err(RuscapeError::LinkExists(..)) => Ok(()),
+        created => created,
+    }
+}
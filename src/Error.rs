@@ -0,0 +1,172 @@
+use crate::Variable::VarType;
+use std::convert::Infallible;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuscapeError {
+    TicksNotStarted,
+    NegativeTickAdvance(f64),
+    EventInPast(f64),
+    DeadAgent(String),
+    UnknownVariable {
+        agent: String,
+        variable: String,
+    },
+    TypeMismatch {
+        agent: String,
+        variable: String,
+        expected: VarType,
+        found: VarType,
+    },
+    WrongType {
+        expected: VarType,
+        found: VarType,
+    },
+    InvalidOperation {
+        op: &'static str,
+        left: VarType,
+        right: VarType,
+    },
+    InvalidUnaryOperation {
+        op: &'static str,
+        operand: VarType,
+    },
+    IntegerOverflow {
+        op: &'static str,
+        left: i64,
+        right: i64,
+    },
+    MissingVars {
+        agent: String,
+        vars: &'static str,
+    },
+    IntegerOutOfRange(i128),
+    EmptyAgentSet,
+    NoSuchTurtle(usize),
+    NoWorld,
+    UnknownBreed(String),
+    LinkBreedDirection(String),
+    LinkExists(usize, usize),
+    SelfLink(usize),
+    InvalidNetwork(String),
+    InvalidDistribution(String),
+    TiedTurtleOutOfBounds(usize),
+}
+
+impl Display for RuscapeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RuscapeError::TicksNotStarted => {
+                write!(
+                    f,
+                    "the tick counter has not been started yet, use reset_ticks"
+                )
+            }
+            RuscapeError::NegativeTickAdvance(amount) => {
+                write!(
+                    f,
+                    "cannot advance the tick counter by a negative amount: {}",
+                    amount
+                )
+            }
+            RuscapeError::EventInPast(time) => {
+                write!(
+                    f,
+                    "cannot schedule or run to time {}, it is in the past",
+                    time
+                )
+            }
+            RuscapeError::DeadAgent(who) => {
+                write!(f, "agent {} is dead", who)
+            }
+            RuscapeError::UnknownVariable { agent, variable } => {
+                write!(
+                    f,
+                    "agent {} does not own a variable named {}",
+                    agent, variable
+                )
+            }
+            RuscapeError::TypeMismatch {
+                agent,
+                variable,
+                expected,
+                found,
+            } => {
+                write!(
+                    f,
+                    "variable {} of agent {} holds {:?} values, not {:?}",
+                    variable, agent, expected, found
+                )
+            }
+            RuscapeError::WrongType { expected, found } => {
+                write!(f, "expected a {:?} value but found {:?}", expected, found)
+            }
+            RuscapeError::InvalidOperation { op, left, right } => {
+                write!(f, "cannot apply {} to {:?} and {:?}", op, left, right)
+            }
+            RuscapeError::InvalidUnaryOperation { op, operand } => {
+                write!(f, "cannot apply {} to {:?}", op, operand)
+            }
+            RuscapeError::IntegerOverflow { op, left, right } => {
+                write!(f, "integer overflow in {} {} {}", left, op, right)
+            }
+            RuscapeError::MissingVars { agent, vars } => {
+                write!(f, "agent {} has no {} attached", agent, vars)
+            }
+            RuscapeError::IntegerOutOfRange(num) => {
+                write!(f, "integer {} is out of range for the target type", num)
+            }
+            RuscapeError::EmptyAgentSet => {
+                write!(f, "cannot pick an agent from an empty agentset")
+            }
+            RuscapeError::NoSuchTurtle(who) => {
+                write!(f, "there is no turtle with who number {}", who)
+            }
+            RuscapeError::NoWorld => {
+                write!(f, "the agent is not attached to a live world")
+            }
+            RuscapeError::UnknownBreed(breed) => {
+                write!(f, "there is no breed named {}", breed)
+            }
+            RuscapeError::LinkBreedDirection(breed) => {
+                write!(
+                    f,
+                    "link breed {} was declared with the other directedness",
+                    breed
+                )
+            }
+            RuscapeError::LinkExists(end1, end2) => {
+                write!(
+                    f,
+                    "a link between turtles {} and {} already exists",
+                    end1, end2
+                )
+            }
+            RuscapeError::SelfLink(who) => {
+                write!(f, "turtle {} cannot link to itself", who)
+            }
+            RuscapeError::InvalidNetwork(reason) => {
+                write!(f, "invalid network parameters: {}", reason)
+            }
+            RuscapeError::InvalidDistribution(reason) => {
+                write!(f, "invalid distribution parameters: {}", reason)
+            }
+            RuscapeError::TiedTurtleOutOfBounds(who) => {
+                write!(
+                    f,
+                    "cannot move, tied turtle {} would be pushed against the edge of the world",
+                    who
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for RuscapeError {}
+
+impl From<Infallible> for RuscapeError {
+    fn from(never: Infallible) -> Self {
+        match never {}
+    }
+}
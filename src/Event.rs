@@ -0,0 +1,156 @@
+use crate::Error::RuscapeError;
+use crate::MapType::HashMap;
+use crate::World::World;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EventId(u64);
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct EventKey {
+    pub(crate) time: f64,
+    pub(crate) priority: i64,
+    id: EventId,
+}
+
+impl Eq for EventKey {}
+
+impl PartialOrd for EventKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// events run by time, then by ascending priority, then in scheduling order
+impl Ord for EventKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.time
+            .partial_cmp(&other.time)
+            .unwrap()
+            .then(self.priority.cmp(&other.priority))
+            .then(self.id.cmp(&other.id))
+    }
+}
+
+pub(crate) struct Event {
+    pub(crate) owner: Option<usize>,
+    pub(crate) action: Box<dyn FnOnce()>,
+}
+
+#[derive(Default)]
+pub(crate) struct EventQueue {
+    next_id: u64,
+    events: BTreeMap<EventKey, Event>,
+    keys: HashMap<EventId, EventKey>,
+}
+
+impl Debug for EventQueue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventQueue")
+            .field("events", &self.events.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl EventQueue {
+    pub(crate) fn push(&mut self, time: f64, priority: i64, event: Event) -> EventId {
+        let id = EventId(self.next_id);
+        self.next_id += 1;
+        let key = EventKey { time, priority, id };
+        self.events.insert(key, event);
+        self.keys.insert(id, key);
+        id
+    }
+
+    pub(crate) fn next_time(&self) -> Option<f64> {
+        self.events.keys().next().map(|key| key.time)
+    }
+
+    pub(crate) fn pop_until(&mut self, time: f64) -> Option<(EventKey, Event)> {
+        let key = *self.events.keys().next().filter(|key| key.time <= time)?;
+        self.keys.swap_remove(&key.id);
+        self.events.remove(&key).map(|event| (key, event))
+    }
+
+    pub(crate) fn cancel(&mut self, id: EventId) -> bool {
+        match self.keys.swap_remove(&id) {
+            Some(key) => self.events.remove(&key).is_some(),
+            None => false,
+        }
+    }
+
+    pub(crate) fn cancel_owned_by(&mut self, who: usize) {
+        let keys = &mut self.keys;
+        self.events.retain(|key, event| {
+            let keep = event.owner != Some(who);
+            if !keep {
+                keys.swap_remove(&key.id);
+            }
+            keep
+        });
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.events.len()
+    }
+}
+
+impl World {
+    pub fn schedule<F: FnOnce() + 'static>(
+        &mut self,
+        time: f64,
+        priority: i64,
+        action: F,
+    ) -> Result<EventId, RuscapeError> {
+        self.schedule_event(time, priority, None, Box::new(action))
+    }
+
+    pub(crate) fn schedule_event(
+        &mut self,
+        time: f64,
+        priority: i64,
+        owner: Option<usize>,
+        action: Box<dyn FnOnce()>,
+    ) -> Result<EventId, RuscapeError> {
+        let now = self.ticks()?;
+        if time.is_nan() || time < now {
+            return Err(RuscapeError::EventInPast(time));
+        }
+        Ok(self.events.push(time, priority, Event { owner, action }))
+    }
+
+    pub fn cancel(&mut self, id: EventId) -> bool {
+        self.events.cancel(id)
+    }
+
+    pub fn next_event_time(&self) -> Option<f64> {
+        self.events.next_time()
+    }
+
+    pub fn pending_events(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn run_until(world: &Rc<RefCell<World>>, time: f64) -> Result<(), RuscapeError> {
+        if !time.is_finite() || time < world.borrow().ticks()? {
+            return Err(RuscapeError::EventInPast(time));
+        }
+        loop {
+            let next = world.borrow_mut().events.pop_until(time);
+            match next {
+                Some((key, event)) => {
+                    world.borrow_mut().ticks = Some(key.time);
+                    (event.action)();
+                }
+                None => break,
+            }
+        }
+        world.borrow_mut().ticks = Some(time);
+        Ok(())
+    }
+}
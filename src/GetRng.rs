@@ -1,5 +1,52 @@
+use crate::Link::LinkId;
+use crate::MapType::HashMap;
 use fastrand::*;
+use fxhash::FxHasher64;
+use std::hash::{Hash, Hasher};
 
 pub trait GetRng {
     fn get_rng(&self) -> Rng;
 }
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RngState {
+    pub(crate) seed: u64,
+    pub(crate) world: u64,
+    pub(crate) turtles: HashMap<usize, u64>,
+    pub(crate) patches: HashMap<(i64, i64), u64>,
+    pub(crate) links: HashMap<LinkId, u64>,
+}
+
+impl RngState {
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+pub(crate) fn agent_seed<I: Hash>(world_seed: u64, who: &I) -> u64 {
+    let mut hasher = FxHasher64::default();
+    world_seed.hash(&mut hasher);
+    who.hash(&mut hasher);
+    hasher.finish()
+}
+
+pub(crate) struct RandRng<'a>(pub(crate) &'a Rng);
+
+impl rand::RngCore for RandRng<'_> {
+    fn next_u32(&mut self) -> u32 {
+        self.0.u32(..)
+    }
+    fn next_u64(&mut self) -> u64 {
+        self.0.u64(..)
+    }
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        dest.chunks_mut(8).for_each(|chunk| {
+            let bytes = self.0.u64(..).to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        })
+    }
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
@@ -0,0 +1,225 @@
+use crate::Agent::{Agent, AgentRef};
+use crate::AgentSet::AgentSet;
+use crate::Error::RuscapeError;
+use crate::MapType::HashMap;
+use crate::World::World;
+use std::cell::RefCell;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::rc::Rc;
+
+// NextReaction keeps each channel's firing time between steps and rescales it when the rate
+// changes, so it draws one random number per step; rates are plain closures without declared
+// dependencies, so every rate is still evaluated on every step, as with Direct
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GillespieMethod {
+    Direct,
+    NextReaction,
+}
+
+// a reaction and one of its members, the agent's who hashed for reactions on agents
+type Channel = (usize, u64);
+
+// the current rate of every member of a reaction
+type Rates = Box<dyn Fn(&Rc<RefCell<World>>) -> Vec<(u64, f64)>>;
+
+struct Reaction {
+    rates: Rates,
+    fire: Box<dyn FnMut(u64)>,
+}
+
+pub struct Gillespie {
+    method: GillespieMethod,
+    reactions: Vec<Reaction>,
+    rates: HashMap<Channel, f64>,
+    times: HashMap<Channel, f64>,
+    last_fired: Option<Channel>,
+}
+
+impl Gillespie {
+    pub fn new(method: GillespieMethod) -> Self {
+        Gillespie {
+            method,
+            reactions: Vec::new(),
+            rates: HashMap::default(),
+            times: HashMap::default(),
+            last_fired: None,
+        }
+    }
+
+    pub fn method(&self) -> GillespieMethod {
+        self.method
+    }
+
+    pub fn len(&self) -> usize {
+        self.reactions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reactions.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.reactions.clear();
+        self.rates.clear();
+        self.times.clear();
+        self.last_fired = None;
+    }
+
+    pub fn add_reaction<R, F>(&mut self, rate: R, mut fire: F) -> &mut Self
+    where
+        R: Fn() -> f64 + 'static,
+        F: FnMut() + 'static,
+    {
+        self.reactions.push(Reaction {
+            rates: Box::new(move |_| vec![(0, rate())]),
+            fire: Box::new(move |_| fire()),
+        });
+        self
+    }
+
+    // `select` is resolved against the world on every step, so agents born later take part
+    // and dead ones drop out
+    pub fn add_agents<A, AS, S, R, F>(&mut self, select: S, rate: R, fire: F) -> &mut Self
+    where
+        A: Agent + 'static,
+        A::IDX: Eq + Hash + Copy + Debug,
+        AS: AgentSet<A>,
+        S: Fn(&World) -> AS + 'static,
+        R: Fn(&AgentRef<A>) -> f64 + 'static,
+        F: Fn(&AgentRef<A>) + 'static,
+    {
+        let members: Rc<RefCell<HashMap<u64, AgentRef<A>>>> = Default::default();
+        let fire_members = members.clone();
+        self.reactions.push(Reaction {
+            rates: Box::new(move |world| {
+                let agents = select(&world.borrow());
+                let mut members = members.borrow_mut();
+                members.clear();
+                agents
+                    .values()
+                    .filter(|agent| agent.is_alive())
+                    .map(|agent| {
+                        let key = fxhash::hash64(&agent.borrow().who());
+                        members.insert(key, agent.clone());
+                        (key, rate(agent))
+                    })
+                    .collect()
+            }),
+            fire: Box::new(move |key| {
+                let agent = fire_members.borrow()[&key].clone();
+                fire(&agent)
+            }),
+        });
+        self
+    }
+
+    pub fn step(&mut self, world: &Rc<RefCell<World>>) -> Result<bool, RuscapeError> {
+        self.advance(world, f64::INFINITY)
+    }
+
+    pub fn run_until(&mut self, world: &Rc<RefCell<World>>, time: f64) -> Result<(), RuscapeError> {
+        while self.advance(world, time)? {}
+        World::run_until(world, time)
+    }
+
+    fn advance(&mut self, world: &Rc<RefCell<World>>, until: f64) -> Result<bool, RuscapeError> {
+        let now = world.borrow().ticks()?;
+        let rates = self
+            .reactions
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, reaction)| {
+                (reaction.rates)(world)
+                    .into_iter()
+                    .map(move |(key, rate)| ((idx, key), rate))
+            })
+            .collect::<HashMap<Channel, f64>>();
+        let next = match self.method {
+            GillespieMethod::Direct => Self::next_direct(world, now, &rates),
+            GillespieMethod::NextReaction => self.next_reaction(world, now, rates),
+        };
+        let event_time = world.borrow().next_event_time();
+        if let Some(time) = event_time.filter(|&time| {
+            time <= until && !matches!(next, Some((next_time, _)) if next_time <= time)
+        }) {
+            World::run_until(world, time)?;
+            return Ok(true);
+        }
+        match next {
+            Some((time, channel)) if time <= until => {
+                world.borrow_mut().ticks = Some(time);
+                (self.reactions[channel.0].fire)(channel.1);
+                self.last_fired = Some(channel);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    fn next_direct(
+        world: &Rc<RefCell<World>>,
+        now: f64,
+        rates: &HashMap<Channel, f64>,
+    ) -> Option<(f64, Channel)> {
+        let total: f64 = rates.values().filter(|&&rate| rate > 0.).sum();
+        if total <= 0. {
+            return None;
+        }
+        let time = now + exponential(world, total);
+        let target = world.borrow().rng().f64() * total;
+        let mut cumulative = 0.;
+        let idx = rates
+            .values()
+            .position(|&rate| {
+                cumulative += rate.max(0.);
+                rate > 0. && cumulative > target
+            })
+            .unwrap_or_else(|| rates.values().rposition(|&rate| rate > 0.).unwrap());
+        rates.get_index(idx).map(|(&channel, _)| (time, channel))
+    }
+
+    fn next_reaction(
+        &mut self,
+        world: &Rc<RefCell<World>>,
+        now: f64,
+        rates: HashMap<Channel, f64>,
+    ) -> Option<(f64, Channel)> {
+        let times = rates
+            .iter()
+            .map(|(&channel, &new)| {
+                let time = match (self.rates.get(&channel), self.times.get(&channel)) {
+                    (Some(&old), Some(&time))
+                        if Some(channel) != self.last_fired && old > 0. && time.is_finite() =>
+                    {
+                        if new <= 0. {
+                            f64::INFINITY
+                        } else if old != new {
+                            now + old / new * (time - now)
+                        } else {
+                            time
+                        }
+                    }
+                    _ => now + exponential(world, new),
+                };
+                (channel, time)
+            })
+            .collect();
+        self.times = times;
+        self.rates = rates;
+        self.last_fired = None;
+        self.times
+            .iter()
+            .filter(|(_, time)| time.is_finite())
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+            .map(|(&channel, &time)| (time, channel))
+    }
+}
+
+fn exponential(world: &Rc<RefCell<World>>, rate: f64) -> f64 {
+    if rate <= 0. {
+        f64::INFINITY
+    } else {
+        -(1. - world.borrow().rng().f64()).ln() / rate
+    }
+}
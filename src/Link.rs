@@ -3,19 +3,47 @@ use crate::LinkRef::LinkRef;
 use crate::LinkSet::LinkSet;
 use crate::World::{World, WorldRef};
 
-use crate::MapType::VariableMap;
-use petgraph::graph::NodeIndex;
+use crate::AgentVars::AgentVars;
+use crate::Error::RuscapeError;
+use crate::MapType::{VariableMap, VariableSchema};
+use crate::Tie::TieMode;
+use petgraph::graph::{EdgeIndex, NodeIndex};
 use std::cell::RefCell;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
 use std::ops::{Deref, DerefMut};
 use std::rc::Rc;
 
+// links of different breeds may join the same turtles, so the breed is part of the identity
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LinkId {
+    pub end1: usize,
+    pub end2: usize,
+    breed: Option<usize>,
+}
+
+impl Debug for LinkId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&(self.end1, self.end2), f)
+    }
+}
+
 pub struct Link {
     w: Option<WorldRef>,
+    end1: usize,
+    end2: usize,
     nid1: NodeIndex,
     nid2: NodeIndex,
+    eid: EdgeIndex,
+    directed: bool,
+    alive: bool,
+    tie_mode: TieMode,
+    breed: Option<String>,
+    breed_id: Option<usize>,
     variables: VariableMap,
+    shadow: Option<VariableMap>,
+    schema: VariableSchema,
+    vars: Option<Box<dyn AgentVars>>,
 }
 
 impl Deref for Link {
@@ -36,9 +64,20 @@ impl Default for Link {
     fn default() -> Self {
         Link {
             w: None,
+            end1: Default::default(),
+            end2: Default::default(),
             nid1: Default::default(),
             nid2: Default::default(),
+            eid: EdgeIndex::end(),
+            directed: false,
+            alive: true,
+            tie_mode: TieMode::None,
+            breed: None,
+            breed_id: None,
             variables: Default::default(),
+            shadow: None,
+            schema: VariableSchema::default(),
+            vars: None,
         }
     }
 }
@@ -46,20 +85,100 @@ impl Default for Link {
 impl Debug for Link {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("Link")
-            .field("nid1", &self.nid1)
-            .field("nid2", &self.nid2)
+            .field("end1", &self.end1)
+            .field("end2", &self.end2)
+            .field("directed", &self.directed)
+            .field("breed", &self.breed)
             .finish()
     }
 }
 
+impl Link {
+    pub(crate) fn new(
+        w: &Rc<RefCell<World>>,
+        (end1, nid1): (usize, NodeIndex),
+        (end2, nid2): (usize, NodeIndex),
+        directed: bool,
+        breed: Option<(&str, usize)>,
+    ) -> Self {
+        Link {
+            w: Some(WorldRef::new(w)),
+            end1,
+            end2,
+            nid1,
+            nid2,
+            directed,
+            breed: breed.map(|(name, _)| name.to_string()),
+            breed_id: breed.map(|(_, id)| id),
+            ..Default::default()
+        }
+    }
+    pub fn id(&self) -> LinkId {
+        LinkId {
+            end1: self.end1,
+            end2: self.end2,
+            breed: self.breed_id,
+        }
+    }
+    pub(crate) fn set_eid(&mut self, eid: EdgeIndex) {
+        self.eid = eid;
+    }
+    pub(crate) fn set_dead(&mut self) {
+        self.alive = false;
+        self.clear_variables();
+    }
+    pub fn nids(&self) -> (NodeIndex, NodeIndex) {
+        (self.nid1, self.nid2)
+    }
+    pub fn eid(&self) -> EdgeIndex {
+        self.eid
+    }
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+    pub fn tie_mode(&self) -> TieMode {
+        self.tie_mode
+    }
+    pub(crate) fn set_tie_mode(&mut self, mode: TieMode) {
+        self.tie_mode = mode;
+    }
+    pub fn breed(&self) -> Option<&str> {
+        self.breed.as_deref()
+    }
+}
+
 impl Agent for Link {
-    type IDX = (usize, usize);
+    type IDX = LinkId;
 
     fn who(&self) -> Self::IDX {
-        (self.nid1.index(), self.nid2.index())
+        self.id()
+    }
+    fn try_world(&self) -> Result<Rc<RefCell<World>>, RuscapeError> {
+        self.w
+            .as_ref()
+            .and_then(|w| w.upgrade())
+            .ok_or(RuscapeError::NoWorld)
+    }
+    fn is_alive(&self) -> bool {
+        self.alive
     }
-    fn world(&self) -> Rc<RefCell<World>> {
-        self.w.as_ref().unwrap().upgrade().unwrap()
+    fn shadow(&self) -> &Option<VariableMap> {
+        &self.shadow
+    }
+    fn shadow_mut(&mut self) -> &mut Option<VariableMap> {
+        &mut self.shadow
+    }
+    fn schema(&self) -> &VariableSchema {
+        &self.schema
+    }
+    fn schema_mut(&mut self) -> &mut VariableSchema {
+        &mut self.schema
+    }
+    fn vars(&self) -> Option<&dyn AgentVars> {
+        self.vars.as_deref()
+    }
+    fn vars_mut(&mut self) -> &mut Option<Box<dyn AgentVars>> {
+        &mut self.vars
     }
 }
 
@@ -73,16 +192,36 @@ impl Link {
 
 impl Link {
     pub fn to_ref(&self) -> LinkRef {
-        unimplemented!()
+        if !self.alive {
+            panic!("{}", RuscapeError::DeadAgent(format!("{:?}", self.who())));
+        }
+        let world = self.world();
+        let world = world.borrow();
+        if self.directed {
+            world.dg[self.eid].clone()
+        } else {
+            world.ug[self.eid].clone()
+        }
     }
 }
 impl Clone for Link {
     fn clone(&self) -> Self {
         Self {
-            w: Some(WorldRef::new(&self.world())),
+            w: self.w.clone(),
+            end1: self.end1,
+            end2: self.end2,
             nid1: self.nid1,
             nid2: self.nid2,
+            eid: self.eid,
+            directed: self.directed,
+            alive: self.alive,
+            tie_mode: self.tie_mode,
+            breed: self.breed.clone(),
+            breed_id: self.breed_id,
             variables: self.variables.clone(),
+            shadow: None,
+            schema: self.schema.clone(),
+            vars: self.vars.as_ref().map(|vars| vars.clone_vars()),
         }
     }
 }
@@ -1,4 +1,91 @@
-use crate::Agent::AgentRef;
-use crate::Link::Link;
+use crate::Agent::{Agent, AgentRef};
+use crate::GetRng::agent_seed;
+use crate::Link::{Link, LinkId};
+use crate::Tie::TieMode;
+use crate::TurtleRef::TurtleRef;
+use crate::TurtleSet::TurtleSet;
+use crate::World::World;
+use std::cell::RefCell;
+use std::rc::Rc;
 
-pub(crate) type LinkRef = AgentRef<Link>;
+pub type LinkRef = AgentRef<Link>;
+
+impl LinkRef {
+    #[inline]
+    pub fn who(&self) -> (usize, usize) {
+        let id = self.id();
+        (id.end1, id.end2)
+    }
+    #[inline]
+    pub fn id(&self) -> LinkId {
+        self.borrow().id()
+    }
+    #[inline]
+    pub fn is_directed(&self) -> bool {
+        self.borrow().is_directed()
+    }
+    #[inline]
+    pub fn breed(&self) -> Option<String> {
+        self.borrow().breed().map(String::from)
+    }
+    #[inline]
+    pub(crate) fn world(&self) -> Rc<RefCell<World>> {
+        self.borrow().world()
+    }
+    pub(crate) fn seed_link_rng(&self, world_seed: u64) {
+        self.rng().seed(agent_seed(
+            world_seed,
+            &("link", self.is_directed(), self.id()),
+        ));
+    }
+}
+
+impl LinkRef {
+    pub fn end1(&self) -> TurtleRef {
+        self.assert_alive();
+        let nid1 = self.borrow().nids().0;
+        self.world().borrow().ug[nid1].clone()
+    }
+    pub fn end2(&self) -> TurtleRef {
+        self.assert_alive();
+        let nid2 = self.borrow().nids().1;
+        self.world().borrow().ug[nid2].clone()
+    }
+    pub fn both_ends(&self) -> TurtleSet {
+        vec![self.end1(), self.end2()].into()
+    }
+    pub fn other_end(&self, turtle: &TurtleRef) -> TurtleRef {
+        let (end1, end2) = (self.end1(), self.end2());
+        if *turtle == end1 {
+            end2
+        } else {
+            end1
+        }
+    }
+    pub fn link_length(&self) -> f64 {
+        self.end1().distance(&self.end2())
+    }
+    pub fn link_heading(&self) -> f64 {
+        self.end1().towards(&self.end2())
+    }
+}
+
+impl LinkRef {
+    pub fn die(&self) {
+        if !self.is_alive() {
+            return;
+        }
+        self.borrow_mut().set_dead();
+        let eid = self.borrow().eid();
+        let world = self.world();
+        let mut world = world.borrow_mut();
+        if self.tie_mode() != TieMode::None {
+            world.tied_links -= 1;
+        }
+        if self.is_directed() {
+            world.dg.remove_edge(eid);
+        } else {
+            world.ug.remove_edge(eid);
+        }
+    }
+}
@@ -1,12 +1,14 @@
+use crate::Activation::Activation;
 use crate::Agent::Agent;
 use crate::AgentSet::AgentSet;
 use crate::GetRng::GetRng;
-use crate::Link::Link;
+use crate::Link::{Link, LinkId};
 use fastrand::*;
 
 use crate::World::*;
 
 use crate::common::concat;
+use crate::Error::RuscapeError;
 use crate::LinkRef::LinkRef;
 use crate::MapType::HashMap;
 use std::cell::RefCell;
@@ -16,10 +18,13 @@ use std::iter::FromIterator;
 use std::ops::{Add, AddAssign, Deref, DerefMut};
 use std::rc::Rc;
 
-type LinkCollection = HashMap<(usize, usize), LinkRef>;
+type LinkCollection = HashMap<LinkId, LinkRef>;
+/// `ask` follows the activation of the set or the world like the other agentsets, so links
+/// are visited in random order by default; use `Activation::Sequential` for insertion order.
 pub struct LinkSet {
     w: Option<WorldRef>,
     pub(crate) raw: LinkCollection,
+    pub(crate) activation: Option<Activation>,
 }
 
 impl Deref for LinkSet {
@@ -50,8 +55,14 @@ impl LinkSet {
     pub fn w(&self) -> &Option<WorldRef> {
         &self.w
     }
+    pub fn try_world(&self) -> Result<Rc<RefCell<World>>, RuscapeError> {
+        self.w
+            .as_ref()
+            .and_then(|w| w.upgrade())
+            .ok_or(RuscapeError::NoWorld)
+    }
     pub fn world(&self) -> Rc<RefCell<World>> {
-        self.w.as_ref().unwrap().upgrade().unwrap()
+        self.try_world().unwrap_or_else(|e| panic!("{}", e))
     }
     pub fn raw(&self) -> &LinkCollection {
         &self.raw
@@ -62,22 +73,33 @@ impl LinkSet {
     pub fn new() -> Self {
         let v: LinkCollection = HashMap::default();
 
-        LinkSet { w: None, raw: v }
+        LinkSet {
+            w: None,
+            raw: v,
+            activation: None,
+        }
     }
 }
 
 impl From<LinkCollection> for LinkSet {
     fn from(raw: LinkCollection) -> Self {
+        if raw.is_empty() {
+            return LinkSet::new();
+        }
         let w = raw.values().next().unwrap().borrow().world();
         LinkSet {
             w: Some(WorldRef::new(&w)),
             raw,
+            activation: None,
         }
     }
 }
 
 impl From<Vec<LinkRef>> for LinkSet {
     fn from(raw: Vec<LinkRef>) -> Self {
+        if raw.is_empty() {
+            return LinkSet::new();
+        }
         let w = raw.iter().next().unwrap().borrow().world();
         LinkSet {
             w: Some(WorldRef::new(&w)),
@@ -85,6 +107,7 @@ impl From<Vec<LinkRef>> for LinkSet {
                 .iter()
                 .map(|t| (t.borrow().who(), t.clone()))
                 .collect::<LinkCollection>(),
+            activation: None,
         }
     }
 }
@@ -98,24 +121,21 @@ impl GetRng for LinkSet {
 impl Clone for LinkSet {
     fn clone(&self) -> Self {
         LinkSet {
-            w: Some(self.w().as_ref().unwrap().clone()),
+            w: self.w.clone(),
             raw: self.raw.clone(),
+            activation: self.activation,
         }
     }
 }
 
 impl LinkSet {
-    pub fn ask<F: FnMut(&LinkRef)>(&self, f: F) {
-        self.values().for_each(f);
-    }
-
     pub fn report<T, F: FnMut(&LinkRef) -> T>(&self, f: F) -> Vec<T> {
         self.values().map(f).collect()
     }
 }
 
-impl FromIterator<((usize, usize), LinkRef)> for LinkSet {
-    fn from_iter<T: IntoIterator<Item = ((usize, usize), LinkRef)>>(iter: T) -> Self {
+impl FromIterator<(LinkId, LinkRef)> for LinkSet {
+    fn from_iter<T: IntoIterator<Item = (LinkId, LinkRef)>>(iter: T) -> Self {
         iter.into_iter().collect::<LinkCollection>().into()
     }
 }
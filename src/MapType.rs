@@ -1,5 +1,7 @@
-use crate::Variable::Variable;
+use crate::VarKey::VarKey;
+use crate::Variable::{VarType, Variable};
 use fxhash::FxBuildHasher;
 use indexmap::IndexMap;
 pub(crate) type HashMap<K, V> = IndexMap<K, V, FxBuildHasher>;
-pub(crate) type VariableMap = std::collections::HashMap<&'static str, Variable, FxBuildHasher>;
+pub(crate) type VariableMap = std::collections::HashMap<VarKey, Variable, FxBuildHasher>;
+pub(crate) type VariableSchema = std::collections::HashMap<VarKey, VarType, FxBuildHasher>;
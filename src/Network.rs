@@ -0,0 +1,264 @@
+use crate::Error::RuscapeError;
+use crate::TurtleSet::TurtleSet;
+use crate::World::World;
+use fastrand::Rng;
+use fxhash::FxBuildHasher;
+use itertools::Itertools;
+use std::collections::HashSet;
+
+type Edges = Vec<(usize, usize)>;
+
+fn invalid(reason: &str) -> RuscapeError {
+    RuscapeError::InvalidNetwork(reason.to_string())
+}
+
+fn check_probability(p: f64) -> Result<(), RuscapeError> {
+    if (0. ..=1.).contains(&p) {
+        Ok(())
+    } else {
+        Err(invalid("link probabilities must be between 0 and 1"))
+    }
+}
+
+impl World {
+    pub fn erdos_renyi(
+        &mut self,
+        n: usize,
+        p: f64,
+        breed: Option<&str>,
+    ) -> Result<TurtleSet, RuscapeError> {
+        check_probability(p)?;
+        let edges = (0..n)
+            .tuple_combinations()
+            .filter(|_| self.rng().f64() < p)
+            .collect_vec();
+        self.build_network(n, edges, breed)
+    }
+
+    pub fn barabasi_albert(
+        &mut self,
+        n: usize,
+        m: usize,
+        breed: Option<&str>,
+    ) -> Result<TurtleSet, RuscapeError> {
+        if m == 0 || m >= n {
+            return Err(invalid("barabasi-albert needs 1 <= m < n"));
+        }
+        let mut edges = vec![];
+        let mut targets = (0..m).collect_vec();
+        // every node appears here once per link end, so sampling from it is preferential
+        let mut repeated = vec![];
+        for source in m..n {
+            edges.extend(targets.iter().map(|&target| (target, source)));
+            repeated.extend(targets.iter().cloned());
+            repeated.extend((0..m).map(|_| source));
+            let mut chosen = HashSet::with_capacity_and_hasher(m, FxBuildHasher::default());
+            while chosen.len() < m {
+                chosen.insert(repeated[self.rng().usize(..repeated.len())]);
+            }
+            targets = chosen.into_iter().sorted().collect_vec();
+        }
+        self.build_network(n, edges, breed)
+    }
+
+    pub fn watts_strogatz(
+        &mut self,
+        n: usize,
+        k: usize,
+        p: f64,
+        breed: Option<&str>,
+    ) -> Result<TurtleSet, RuscapeError> {
+        check_probability(p)?;
+        let edges = ring_edges(n, k)?;
+        let mut adjacency = vec![HashSet::<usize, FxBuildHasher>::default(); n];
+        edges.iter().for_each(|&(a, b)| {
+            adjacency[a].insert(b);
+            adjacency[b].insert(a);
+        });
+        for j in 1..=k / 2 {
+            for a in 0..n {
+                let b = (a + j) % n;
+                if self.rng().f64() >= p || adjacency[a].len() >= n - 1 {
+                    continue;
+                }
+                let mut c = self.rng().usize(..n);
+                while c == a || adjacency[a].contains(&c) {
+                    c = self.rng().usize(..n);
+                }
+                adjacency[a].remove(&b);
+                adjacency[b].remove(&a);
+                adjacency[a].insert(c);
+                adjacency[c].insert(a);
+            }
+        }
+        let edges = adjacency
+            .iter()
+            .enumerate()
+            .flat_map(|(a, neighbors)| {
+                neighbors
+                    .iter()
+                    .filter(move |&&b| a < b)
+                    .map(move |&b| (a, b))
+            })
+            .sorted()
+            .collect_vec();
+        self.build_network(n, edges, breed)
+    }
+
+    pub fn ring_lattice(
+        &mut self,
+        n: usize,
+        k: usize,
+        breed: Option<&str>,
+    ) -> Result<TurtleSet, RuscapeError> {
+        let edges = ring_edges(n, k)?;
+        self.build_network(n, edges, breed)
+    }
+
+    pub fn grid_lattice(
+        &mut self,
+        width: usize,
+        height: usize,
+        periodic: bool,
+        breed: Option<&str>,
+    ) -> Result<TurtleSet, RuscapeError> {
+        let index = |x: usize, y: usize| y * width + x;
+        let mut edges = vec![];
+        for y in 0..height {
+            for x in 0..width {
+                if x + 1 < width {
+                    edges.push((index(x, y), index(x + 1, y)));
+                } else if periodic && width > 2 {
+                    edges.push((index(0, y), index(x, y)));
+                }
+                if y + 1 < height {
+                    edges.push((index(x, y), index(x, y + 1)));
+                } else if periodic && height > 2 {
+                    edges.push((index(x, 0), index(x, y)));
+                }
+            }
+        }
+        self.build_network(width * height, edges, breed)
+    }
+
+    pub fn complete_network(
+        &mut self,
+        n: usize,
+        breed: Option<&str>,
+    ) -> Result<TurtleSet, RuscapeError> {
+        let edges = (0..n).tuple_combinations().collect_vec();
+        self.build_network(n, edges, breed)
+    }
+
+    pub fn star_network(
+        &mut self,
+        n: usize,
+        breed: Option<&str>,
+    ) -> Result<TurtleSet, RuscapeError> {
+        let edges = (1..n).map(|leaf| (0, leaf)).collect_vec();
+        self.build_network(n, edges, breed)
+    }
+
+    pub fn random_regular(
+        &mut self,
+        n: usize,
+        d: usize,
+        breed: Option<&str>,
+    ) -> Result<TurtleSet, RuscapeError> {
+        if d >= n.max(1) || n * d % 2 == 1 {
+            return Err(invalid("random-regular needs d < n and an even n * d"));
+        }
+        let edges = (0..1000)
+            .find_map(|_| try_regular_edges(self.rng(), n, d))
+            .ok_or_else(|| invalid("could not pair the stubs of a random-regular network"))?;
+        self.build_network(n, edges, breed)
+    }
+
+    pub fn stochastic_block_model(
+        &mut self,
+        sizes: &[usize],
+        probs: &[Vec<f64>],
+        breed: Option<&str>,
+    ) -> Result<TurtleSet, RuscapeError> {
+        if probs.len() != sizes.len() || probs.iter().any(|row| row.len() != sizes.len()) {
+            return Err(invalid(
+                "stochastic block model needs a block-by-block probability matrix",
+            ));
+        }
+        probs
+            .iter()
+            .flatten()
+            .try_for_each(|&p| check_probability(p))?;
+        let blocks = sizes
+            .iter()
+            .enumerate()
+            .flat_map(|(block, &size)| (0..size).map(move |_| block))
+            .collect_vec();
+        let edges = (0..blocks.len())
+            .tuple_combinations()
+            .filter(|&(a, b)| self.rng().f64() < probs[blocks[a]][blocks[b]])
+            .collect_vec();
+        self.build_network(blocks.len(), edges, breed)
+    }
+
+    fn build_network(
+        &mut self,
+        n: usize,
+        edges: Edges,
+        breed: Option<&str>,
+    ) -> Result<TurtleSet, RuscapeError> {
+        if let Some(breed) = breed {
+            self.try_breed(breed)?;
+        }
+        let turtles = self.crt(n).values().cloned().collect_vec();
+        if breed.is_some() {
+            turtles
+                .iter()
+                .try_for_each(|t| self.assign_breed(t, breed))?;
+        }
+        edges.into_iter().try_for_each(|(a, b)| {
+            let (nid1, nid2) = (turtles[a].borrow().nid(), turtles[b].borrow().nid());
+            self.add_link(nid1, nid2, false, None).map(|_| ())
+        })?;
+        Ok(turtles.into())
+    }
+}
+
+fn ring_edges(n: usize, k: usize) -> Result<Edges, RuscapeError> {
+    if k % 2 == 1 || k >= n.max(1) {
+        return Err(invalid("ring lattices need an even k < n"));
+    }
+    Ok((1..=k / 2)
+        .flat_map(|j| (0..n).map(move |a| (a, (a + j) % n)))
+        .collect_vec())
+}
+
+fn try_regular_edges(rng: &Rng, n: usize, d: usize) -> Option<Edges> {
+    let mut edges = HashSet::<(usize, usize), FxBuildHasher>::default();
+    let mut stubs = (0..n)
+        .flat_map(|node| (0..d).map(move |_| node))
+        .collect_vec();
+    while !stubs.is_empty() {
+        rng.shuffle(&mut stubs);
+        let mut leftover = vec![];
+        for (&a, &b) in stubs.iter().tuples() {
+            let edge = (a.min(b), a.max(b));
+            if a != b && !edges.contains(&edge) {
+                edges.insert(edge);
+            } else {
+                leftover.push(a);
+                leftover.push(b);
+            }
+        }
+        // give up on this attempt once no leftover stubs can be paired any more
+        let suitable = leftover
+            .iter()
+            .tuple_combinations()
+            .any(|(&a, &b)| a != b && !edges.contains(&(a.min(b), a.max(b))));
+        if !leftover.is_empty() && !suitable {
+            return None;
+        }
+        stubs = leftover;
+    }
+    Some(edges.into_iter().sorted().collect_vec())
+}
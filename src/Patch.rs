@@ -12,7 +12,9 @@ use crate::TurtleRef::TurtleRef;
 
 use crate::TurtleSet::TurtleSet;
 
-use crate::MapType::VariableMap;
+use crate::AgentVars::AgentVars;
+use crate::Error::RuscapeError;
+use crate::MapType::{VariableMap, VariableSchema};
 use std::cell::RefCell;
 use std::cmp::PartialEq;
 use std::fmt;
@@ -31,6 +33,9 @@ pub struct Patch {
     pub(crate) neighborhood4: PatchSet,
     pub(crate) neighbors4: PatchSet,
     variables: VariableMap,
+    shadow: Option<VariableMap>,
+    schema: VariableSchema,
+    vars: Option<Box<dyn AgentVars>>,
     turtles_on: TurtleSet,
 }
 impl Deref for Patch {
@@ -113,8 +118,29 @@ impl Agent for Patch {
     fn who(&self) -> Self::IDX {
         (self.pxcor, self.pycor)
     }
-    fn world(&self) -> Rc<RefCell<World>> {
-        self.w.as_ref().unwrap().upgrade().unwrap()
+    fn try_world(&self) -> Result<Rc<RefCell<World>>, RuscapeError> {
+        self.w
+            .as_ref()
+            .and_then(|w| w.upgrade())
+            .ok_or(RuscapeError::NoWorld)
+    }
+    fn shadow(&self) -> &Option<VariableMap> {
+        &self.shadow
+    }
+    fn shadow_mut(&mut self) -> &mut Option<VariableMap> {
+        &mut self.shadow
+    }
+    fn schema(&self) -> &VariableSchema {
+        &self.schema
+    }
+    fn schema_mut(&mut self) -> &mut VariableSchema {
+        &mut self.schema
+    }
+    fn vars(&self) -> Option<&dyn AgentVars> {
+        self.vars.as_deref()
+    }
+    fn vars_mut(&mut self) -> &mut Option<Box<dyn AgentVars>> {
+        &mut self.vars
     }
 }
 impl Patch {
@@ -157,6 +183,9 @@ impl Patch {
             neighborhood4: Default::default(),
             neighbors4: Default::default(),
             variables: VariableMap::default(),
+            shadow: None,
+            schema: VariableSchema::default(),
+            vars: None,
             turtles_on: Default::default(),
         }
     }
@@ -181,6 +210,9 @@ impl Clone for Patch {
             neighborhood4: self.neighborhood4.clone(),
             neighbors4: self.neighbors4.clone(),
             variables: self.variables.clone(),
+            shadow: None,
+            schema: self.schema.clone(),
+            vars: self.vars.as_ref().map(|vars| vars.clone_vars()),
             turtles_on: self.turtles_on.clone(),
         }
     }
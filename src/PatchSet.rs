@@ -1,3 +1,4 @@
+use crate::Activation::Activation;
 use crate::Agent::Agent;
 use crate::GetRng::GetRng;
 use crate::Patch::*;
@@ -9,6 +10,7 @@ use crate::PatchRef::PatchRef;
 use fastrand::Rng;
 
 use crate::common::concat;
+use crate::Error::RuscapeError;
 use crate::MapType::HashMap;
 use macroquad::drawing::*;
 use std::cell::RefCell;
@@ -27,6 +29,7 @@ type PatchCollection = HashMap<(i64, i64), PatchRef>;
 pub struct PatchSet {
     w: Option<WorldRef>,
     pub(crate) patches: PatchCollection,
+    pub(crate) activation: Option<Activation>,
 }
 
 impl PatchSet {
@@ -64,9 +67,14 @@ impl DerefMut for PatchSet {
 }
 
 impl PatchSet {
+    pub fn try_world(&self) -> Result<Rc<RefCell<World>>, RuscapeError> {
+        self.w
+            .as_ref()
+            .and_then(|w| w.upgrade())
+            .ok_or(RuscapeError::NoWorld)
+    }
     pub(crate) fn world(&self) -> Rc<RefCell<World>> {
-        let world = self.w.as_ref().unwrap().upgrade().unwrap().clone();
-        world
+        self.try_world().unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
@@ -89,7 +97,11 @@ impl PatchSet {
                     .collect();
                 Self::set_neighbors(max_pxcor, max_pycor, origin_location, &patches);
                 Self::set_neighbors4(max_pxcor, max_pycor, origin_location, &patches);
-                PatchSet { w: None, patches }
+                PatchSet {
+                    w: None,
+                    patches,
+                    activation: None,
+                }
             }
             OriginLocation::Corner => {
                 let patch_iter = (0..=max_pxcor)
@@ -102,7 +114,11 @@ impl PatchSet {
                     .collect();
                 Self::set_neighbors(max_pxcor, max_pycor, origin_location, &patches);
                 Self::set_neighbors4(max_pxcor, max_pycor, origin_location, &patches);
-                PatchSet { w: None, patches }
+                PatchSet {
+                    w: None,
+                    patches,
+                    activation: None,
+                }
             }
         }
     }
@@ -234,12 +250,19 @@ impl From<HashMap<(i64, i64), PatchRef>> for PatchSet {
         }
         let (_, patch1) = patches.iter().next().unwrap().clone();
         let w = Option::clone(patch1.borrow().w());
-        PatchSet { w, patches }
+        PatchSet {
+            w,
+            patches,
+            activation: None,
+        }
     }
 }
 
 impl From<Vec<PatchRef>> for PatchSet {
     fn from(patches: Vec<PatchRef>) -> Self {
+        if patches.is_empty() {
+            return PatchSet::default();
+        }
         let w = patches.iter().next().unwrap().borrow().world();
         PatchSet {
             w: Some(WorldRef::new(&w)),
@@ -247,6 +270,7 @@ impl From<Vec<PatchRef>> for PatchSet {
                 .iter()
                 .map(|p| (p.borrow().who(), p.clone()))
                 .collect(),
+            activation: None,
         }
     }
 }
@@ -261,6 +285,7 @@ impl Default for PatchSet {
         PatchSet {
             w: None,
             patches: HashMap::<(i64, i64), PatchRef>::default(),
+            activation: None,
         }
     }
 }
@@ -276,16 +301,10 @@ impl World {
 
 impl Clone for PatchSet {
     fn clone(&self) -> Self {
-        if self.len() == 0 {
-            PatchSet {
-                w: None,
-                patches: self.patches.clone(),
-            }
-        } else {
-            PatchSet {
-                w: Some(WorldRef::new(&self.world())),
-                patches: self.patches.clone(),
-            }
+        PatchSet {
+            w: self.w.clone(),
+            patches: self.patches.clone(),
+            activation: self.activation,
         }
     }
 }
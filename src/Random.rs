@@ -0,0 +1,106 @@
+use crate::Agent::{Agent, AgentRef};
+use crate::Error::RuscapeError;
+use crate::GetRng::{GetRng, RandRng};
+use crate::World::World;
+use fastrand::Rng;
+use rand::Rng as _;
+use rand_distr::{Binomial, Exp, Gamma, Normal, Poisson};
+
+fn invalid(reason: &str) -> RuscapeError {
+    RuscapeError::InvalidDistribution(reason.to_string())
+}
+
+pub trait Random: GetRng {
+    fn random_float(&self, max: f64) -> f64 {
+        self.get_rng().f64() * max
+    }
+
+    fn random_int(&self, max: i64) -> i64 {
+        match max {
+            0 => 0,
+            max if max > 0 => self.get_rng().i64(0..max),
+            max => -self.get_rng().i64(0..-max),
+        }
+    }
+
+    fn random_normal(&self, mean: f64, std_dev: f64) -> f64 {
+        self.try_random_normal(mean, std_dev)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn try_random_normal(&self, mean: f64, std_dev: f64) -> Result<f64, RuscapeError> {
+        if !mean.is_finite() || !std_dev.is_finite() || std_dev < 0. {
+            return Err(invalid(
+                "normal needs a finite mean and standard deviation >= 0",
+            ));
+        }
+        let normal = Normal::new(mean, std_dev).map_err(|_| invalid("normal"))?;
+        Ok(RandRng(&self.get_rng()).sample(normal))
+    }
+
+    fn random_poisson(&self, mean: f64) -> f64 {
+        self.try_random_poisson(mean)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn try_random_poisson(&self, mean: f64) -> Result<f64, RuscapeError> {
+        if !mean.is_finite() || mean <= 0. {
+            return Err(invalid("poisson needs a finite mean > 0"));
+        }
+        let poisson = Poisson::new(mean).map_err(|_| invalid("poisson"))?;
+        Ok(RandRng(&self.get_rng()).sample::<f64, _>(poisson))
+    }
+
+    fn random_exponential(&self, mean: f64) -> f64 {
+        self.try_random_exponential(mean)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn try_random_exponential(&self, mean: f64) -> Result<f64, RuscapeError> {
+        if !mean.is_finite() || mean <= 0. {
+            return Err(invalid("exponential needs a finite mean > 0"));
+        }
+        let exp = Exp::new(1. / mean).map_err(|_| invalid("exponential"))?;
+        Ok(RandRng(&self.get_rng()).sample(exp))
+    }
+
+    fn random_gamma(&self, alpha: f64, lambda: f64) -> f64 {
+        self.try_random_gamma(alpha, lambda)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn try_random_gamma(&self, alpha: f64, lambda: f64) -> Result<f64, RuscapeError> {
+        if !alpha.is_finite() || !lambda.is_finite() || alpha <= 0. || lambda <= 0. {
+            return Err(invalid("gamma needs a finite alpha > 0 and lambda > 0"));
+        }
+        let gamma = Gamma::new(alpha, 1. / lambda).map_err(|_| invalid("gamma"))?;
+        Ok(RandRng(&self.get_rng()).sample(gamma))
+    }
+
+    fn random_binomial(&self, n: u64, p: f64) -> u64 {
+        self.try_random_binomial(n, p)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn try_random_binomial(&self, n: u64, p: f64) -> Result<u64, RuscapeError> {
+        if !(0. ..=1.).contains(&p) {
+            return Err(invalid("binomial needs a probability between 0 and 1"));
+        }
+        let binomial = Binomial::new(n, p).map_err(|_| invalid("binomial"))?;
+        Ok(RandRng(&self.get_rng()).sample(binomial))
+    }
+}
+
+impl<T: GetRng> Random for T {}
+
+impl GetRng for World {
+    fn get_rng(&self) -> Rng {
+        self.rng().clone()
+    }
+}
+
+impl<A: Agent> GetRng for AgentRef<A> {
+    fn get_rng(&self) -> Rng {
+        self.borrow().world().borrow().rng().clone()
+    }
+}
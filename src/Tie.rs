@@ -0,0 +1,119 @@
+use crate::Error::RuscapeError;
+use crate::LinkRef::LinkRef;
+use crate::Toroidal::toroidal_delta;
+use crate::TurtleRef::TurtleRef;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use std::collections::{HashSet, VecDeque};
+
+// a tied turtle with where it ends up and how far it turns
+type TiedMove = (TurtleRef, (f64, f64), f64);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TieMode {
+    None,
+    Fixed,
+    Free,
+}
+
+impl LinkRef {
+    #[inline]
+    pub fn tie_mode(&self) -> TieMode {
+        self.borrow().tie_mode()
+    }
+    pub fn set_tie_mode(&self, mode: TieMode) {
+        self.assert_alive();
+        let was_tied = self.tie_mode() != TieMode::None;
+        self.borrow_mut().set_tie_mode(mode);
+        let world = self.world();
+        let mut world = world.borrow_mut();
+        match (was_tied, mode != TieMode::None) {
+            (false, true) => world.tied_links += 1,
+            (true, false) => world.tied_links -= 1,
+            _ => {}
+        }
+    }
+    pub fn tie(&self) {
+        self.set_tie_mode(TieMode::Fixed)
+    }
+    pub fn untie(&self) {
+        self.set_tie_mode(TieMode::None)
+    }
+}
+
+impl TurtleRef {
+    // moves this turtle to `to` and turns it clockwise by `degree`, carrying the tied turtles
+    // along; nothing moves if a tied turtle would be pushed against the edge of the world
+    pub(crate) fn move_with_ties(&self, to: (f64, f64), degree: f64) -> Result<(), RuscapeError> {
+        let to = self.borrow().wrap(to.0, to.1);
+        let tied = if self.world().borrow().tied_links == 0 {
+            vec![]
+        } else {
+            self.tied_moves(to, degree)?
+        };
+        self.place(to.0, to.1);
+        if degree != 0. {
+            self.borrow_mut().right(degree);
+        }
+        tied.into_iter().for_each(|(t, (x, y), degree)| {
+            t.place(x, y);
+            if degree != 0. {
+                t.borrow_mut().right(degree);
+            }
+        });
+        Ok(())
+    }
+
+    // where every turtle following this one ends up and how far it turns; each leaf circles
+    // its root by the root's turn, and only a fixed tie passes that turn on to the leaf
+    fn tied_moves(&self, to: (f64, f64), degree: f64) -> Result<Vec<TiedMove>, RuscapeError> {
+        let world = self.world();
+        let world = world.borrow();
+        let periodic = world.is_periodic();
+        let (x_min, x_max, y_min, y_max) = {
+            let root = self.borrow();
+            (root.x_min(), root.x_max(), root.y_min(), root.y_max())
+        };
+        let root = self.borrow().nid();
+        let mut visited = HashSet::new();
+        visited.insert(root);
+        let mut queue = VecDeque::new();
+        queue.push_back((root, (self.xcor(), self.ycor()), to, degree));
+        let mut tied = vec![];
+        while let Some((nid, from, to, degree)) = queue.pop_front() {
+            let (sin, cos) = degree.to_radians().sin_cos();
+            for e in world
+                .ug
+                .edges(nid)
+                .chain(world.dg.edges_directed(nid, Direction::Outgoing))
+            {
+                let mode = e.weight().tie_mode();
+                let other = if e.source() == nid {
+                    e.target()
+                } else {
+                    e.source()
+                };
+                if mode == TieMode::None || !visited.insert(other) {
+                    continue;
+                }
+                let t = world.ug[other].clone();
+                let (rx, ry) = if periodic {
+                    (
+                        toroidal_delta(t.xcor(), from.0, x_max - x_min),
+                        toroidal_delta(t.ycor(), from.1, y_max - y_min),
+                    )
+                } else {
+                    (t.xcor() - from.0, t.ycor() - from.1)
+                };
+                let (x, y) = (to.0 + rx * cos + ry * sin, to.1 - rx * sin + ry * cos);
+                if !periodic && (x < x_min || x > x_max || y < y_min || y > y_max) {
+                    return Err(RuscapeError::TiedTurtleOutOfBounds(t.who()));
+                }
+                let turn = if mode == TieMode::Fixed { degree } else { 0. };
+                queue.push_back((other, (t.xcor(), t.ycor()), (x, y), turn));
+                tied.push((t, (x, y), turn));
+            }
+        }
+        Ok(tied)
+    }
+}
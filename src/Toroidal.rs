@@ -9,6 +9,17 @@ pub(crate) fn toroidal_distance(val1: f64, val2: f64, dim: f64) -> f64 {
     }
 }
 
+pub(crate) fn toroidal_delta(val1: f64, val2: f64, dim: f64) -> f64 {
+    let delta = val1 - val2;
+    if delta > dim / 2.0 {
+        delta - dim
+    } else if delta < -dim / 2.0 {
+        delta + dim
+    } else {
+        delta
+    }
+}
+
 pub(crate) fn toroidal_transform(val: f64, min: f64, max: f64) -> f64 {
     if val >= min && val <= max {
         val
@@ -1,6 +1,7 @@
 use crate::Agent::Agent;
 use crate::AgentSet::AgentSet;
 
+use crate::LinkRef::LinkRef;
 use crate::LinkSet::LinkSet;
 
 use crate::PatchRef::PatchRef;
@@ -21,13 +22,18 @@ use std::fmt::{Debug, Formatter};
 
 use crate::Toroidal::*;
 
-use crate::MapType::VariableMap;
+use crate::AgentVars::AgentVars;
+use crate::Error::RuscapeError;
+use crate::MapType::{VariableMap, VariableSchema};
 use std::ops::{Deref, DerefMut};
 use std::rc::Rc;
 
 pub struct Turtle {
     w: Option<WorldRef>,
     who: usize,
+    nid: NodeIndex,
+    alive: bool,
+    breed: Option<String>,
     color: Color,
     heading: f64,
     xcor: f64,
@@ -42,6 +48,9 @@ pub struct Turtle {
     shape: String,
     size: f64,
     variables: VariableMap,
+    shadow: Option<VariableMap>,
+    schema: VariableSchema,
+    vars: Option<Box<dyn AgentVars>>,
 }
 
 impl Turtle {
@@ -118,6 +127,16 @@ impl Turtle {
     pub(crate) fn set_who(&mut self, who: usize) {
         self.who = who;
     }
+    pub(crate) fn set_nid(&mut self, nid: NodeIndex) {
+        self.nid = nid;
+    }
+    pub(crate) fn set_dead(&mut self) {
+        self.alive = false;
+        self.clear_variables();
+    }
+    pub(crate) fn set_breed(&mut self, breed: Option<String>) {
+        self.breed = breed;
+    }
     pub fn set_color(&mut self, color: Color) {
         self.color = color;
     }
@@ -138,18 +157,25 @@ impl Turtle {
     }
 
     pub(crate) fn setxy(&mut self, x: f64, y: f64) {
+        let (x, y) = self.wrap(x, y);
+        self.set_xcor(x);
+        self.set_ycor(y);
+    }
+    // where the turtle actually lands when sent to (x, y)
+    pub(crate) fn wrap(&self, x: f64, y: f64) -> (f64, f64) {
         if self.world().borrow().is_periodic() {
-            self.set_xcor(toroidal_transform(x, self.x_min, self.x_max));
-            self.set_ycor(toroidal_transform(y, self.y_min, self.y_max));
+            (
+                toroidal_transform(x, self.x_min, self.x_max),
+                toroidal_transform(y, self.y_min, self.y_max),
+            )
         } else {
-            self.set_xcor(x.min(self.x_max).max(self.x_min));
-            self.set_ycor(y.min(self.y_max).max(self.y_min));
+            (
+                x.min(self.x_max).max(self.x_min),
+                y.min(self.y_max).max(self.y_min),
+            )
         }
     }
 
-    pub(crate) fn left(&mut self, degree: f64) {
-        self.set_heading(self.heading - degree);
-    }
     pub(crate) fn right(&mut self, degree: f64) {
         self.set_heading(self.heading + degree);
     }
@@ -191,7 +217,10 @@ impl Turtle {
         self.size
     }
     pub fn nid(&self) -> NodeIndex {
-        NodeIndex::new(self.who)
+        self.nid
+    }
+    pub fn breed(&self) -> Option<&str> {
+        self.breed.as_deref()
     }
 
     pub fn patch_here(&self) -> PatchRef {
@@ -204,6 +233,9 @@ impl Default for Turtle {
         Turtle {
             w: None,
             who: Default::default(),
+            nid: NodeIndex::end(),
+            alive: true,
+            breed: None,
             color: BLUE,
             heading: 0.0,
             xcor: 0.0,
@@ -218,6 +250,9 @@ impl Default for Turtle {
             shape: "default".to_string(),
             size: 1.0,
             variables: VariableMap::default(),
+            shadow: None,
+            schema: VariableSchema::default(),
+            vars: None,
         }
     }
 }
@@ -229,8 +264,34 @@ impl Agent for Turtle {
         self.who
     }
 
-    fn world(&self) -> Rc<RefCell<World>> {
-        self.w.as_ref().unwrap().upgrade().unwrap()
+    fn try_world(&self) -> Result<Rc<RefCell<World>>, RuscapeError> {
+        self.w
+            .as_ref()
+            .and_then(|w| w.upgrade())
+            .ok_or(RuscapeError::NoWorld)
+    }
+
+    fn is_alive(&self) -> bool {
+        self.alive
+    }
+
+    fn shadow(&self) -> &Option<VariableMap> {
+        &self.shadow
+    }
+    fn shadow_mut(&mut self) -> &mut Option<VariableMap> {
+        &mut self.shadow
+    }
+    fn schema(&self) -> &VariableSchema {
+        &self.schema
+    }
+    fn schema_mut(&mut self) -> &mut VariableSchema {
+        &mut self.schema
+    }
+    fn vars(&self) -> Option<&dyn AgentVars> {
+        self.vars.as_deref()
+    }
+    fn vars_mut(&mut self) -> &mut Option<Box<dyn AgentVars>> {
+        &mut self.vars
     }
 }
 
@@ -241,10 +302,9 @@ impl Turtle {
             .ug
             .neighbors(self.nid())
             .map(|nid| {
-                (
-                    nid.index(),
-                    self.world().borrow().ug.node_weight(nid).unwrap().clone(),
-                )
+                let turtle = self.world().borrow().ug.node_weight(nid).unwrap().clone();
+                let who = turtle.borrow().who();
+                (who, turtle)
             })
             .collect::<TurtleCollection>()
             .into()
@@ -256,10 +316,9 @@ impl Turtle {
             .dg
             .neighbors_directed(self.nid(), dir)
             .map(|nid| {
-                (
-                    nid.index(),
-                    self.world().borrow().dg.node_weight(nid).unwrap().clone(),
-                )
+                let turtle = self.world().borrow().dg.node_weight(nid).unwrap().clone();
+                let who = turtle.borrow().who();
+                (who, turtle)
             })
             .collect::<TurtleCollection>()
             .into()
@@ -272,11 +331,64 @@ impl Turtle {
     pub fn out_link_neighbors(&self) -> TurtleSet {
         self.di_link_neighbors(Direction::Outgoing)
     }
+
+    pub fn is_link_neighbor(&self, other: &TurtleRef) -> bool {
+        self.world()
+            .borrow()
+            .ug
+            .contains_edge(self.nid(), other.borrow().nid())
+    }
+
+    pub fn is_in_link_neighbor(&self, other: &TurtleRef) -> bool {
+        self.world()
+            .borrow()
+            .dg
+            .contains_edge(other.borrow().nid(), self.nid())
+    }
+
+    pub fn is_out_link_neighbor(&self, other: &TurtleRef) -> bool {
+        self.world()
+            .borrow()
+            .dg
+            .contains_edge(self.nid(), other.borrow().nid())
+    }
+
+    pub fn link_with(&self, other: &TurtleRef) -> Option<LinkRef> {
+        let world = self.world();
+        let world = world.borrow();
+        world
+            .ug
+            .find_edge(self.nid(), other.borrow().nid())
+            .map(|eid| world.ug[eid].clone())
+    }
+
+    pub fn in_link_from(&self, other: &TurtleRef) -> Option<LinkRef> {
+        let world = self.world();
+        let world = world.borrow();
+        world
+            .dg
+            .find_edge(other.borrow().nid(), self.nid())
+            .map(|eid| world.dg[eid].clone())
+    }
+
+    pub fn out_link_to(&self, other: &TurtleRef) -> Option<LinkRef> {
+        let world = self.world();
+        let world = world.borrow();
+        world
+            .dg
+            .find_edge(self.nid(), other.borrow().nid())
+            .map(|eid| world.dg[eid].clone())
+    }
     pub fn create_link_with(&self, other: &TurtleRef) {
+        self.try_create_link_with(other)
+            .unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    pub fn try_create_link_with(&self, other: &TurtleRef) -> Result<(), RuscapeError> {
         self.world()
             .borrow_mut()
-            .ug
-            .add_edge(self.nid(), other.borrow().nid(), Default::default());
+            .add_link(self.nid(), other.borrow().nid(), false, None)
+            .map(|_| ())
     }
 
     pub fn create_links_with(&self, other: &mut TurtleSet) {
@@ -284,10 +396,15 @@ impl Turtle {
     }
 
     pub fn create_link_to(&self, other: &TurtleRef) {
+        self.try_create_link_to(other)
+            .unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    pub fn try_create_link_to(&self, other: &TurtleRef) -> Result<(), RuscapeError> {
         self.world()
             .borrow_mut()
-            .dg
-            .add_edge(self.nid(), other.borrow().nid(), Default::default());
+            .add_link(self.nid(), other.borrow().nid(), true, None)
+            .map(|_| ())
     }
 
     pub fn create_links_to(&self, others: &mut TurtleSet) {
@@ -295,10 +412,15 @@ impl Turtle {
     }
 
     pub fn create_link_from(&self, other: &TurtleRef) {
+        self.try_create_link_from(other)
+            .unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    pub fn try_create_link_from(&self, other: &TurtleRef) -> Result<(), RuscapeError> {
         self.world()
             .borrow_mut()
-            .dg
-            .add_edge(other.borrow().nid(), self.nid(), Default::default());
+            .add_link(other.borrow().nid(), self.nid(), true, None)
+            .map(|_| ())
     }
 
     pub fn create_links_from(&self, others: &mut TurtleSet) {
@@ -372,6 +494,9 @@ impl Clone for Turtle {
         Self {
             w: Some(WorldRef::new(&self.world())),
             who: self.who,
+            nid: self.nid,
+            alive: self.alive,
+            breed: self.breed.clone(),
             color: self.color,
             heading: self.heading,
             xcor: self.xcor,
@@ -386,6 +511,9 @@ impl Clone for Turtle {
             shape: self.shape.clone(),
             size: self.size,
             variables: self.variables.clone(),
+            shadow: None,
+            schema: self.schema.clone(),
+            vars: self.vars.as_ref().map(|vars| vars.clone_vars()),
         }
     }
 }
@@ -1,5 +1,6 @@
 use crate::Agent::{Agent, AgentRef};
 
+use crate::LinkRef::LinkRef;
 use crate::LinkSet::LinkSet;
 
 use crate::AgentSet::AgentSet;
@@ -10,19 +11,20 @@ use crate::World::World;
 
 use crate::PatchRef::PatchRef;
 use itertools::Itertools;
+use petgraph::Direction;
 
 use macroquad::Color;
 
 use crate::common::random_float;
-
-use fastrand::Rng;
+use crate::Error::RuscapeError;
+use crate::Event::EventId;
 
 use crate::PatchSet::PatchSet;
-use petgraph::graph::NodeIndex;
+use crate::Tie::TieMode;
 use std::cell::RefCell;
 use std::rc::Rc;
 
-pub(crate) type TurtleRef = AgentRef<Turtle>;
+pub type TurtleRef = AgentRef<Turtle>;
 
 impl TurtleRef {
     #[inline]
@@ -82,7 +84,7 @@ impl TurtleRef {
     #[inline]
     pub fn random_xcor(&self) -> f64 {
         random_float(
-            &mut Rng::new(),
+            self.world().borrow().rng(),
             self.borrow().x_min(),
             self.borrow().x_max(),
         )
@@ -90,19 +92,23 @@ impl TurtleRef {
     #[inline]
     pub fn random_ycor(&self) -> f64 {
         random_float(
-            &mut Rng::new(),
+            self.world().borrow().rng(),
             self.borrow().y_min(),
             self.borrow().y_max(),
         )
     }
     #[inline]
     pub fn random_pxcor(&self) -> i64 {
-        self.rng()
+        self.world()
+            .borrow()
+            .rng()
             .i64(self.borrow().x_min().ceil() as i64..self.borrow().x_max().ceil() as i64)
     }
     #[inline]
     pub fn random_pycor(&self) -> i64 {
-        self.rng()
+        self.world()
+            .borrow()
+            .rng()
             .i64(self.borrow().y_min().ceil() as i64..self.borrow().y_max().ceil() as i64)
     }
 }
@@ -110,6 +116,14 @@ impl TurtleRef {
 impl TurtleRef {
     #[inline]
     pub fn setxy(&self, x: f64, y: f64) -> &TurtleRef {
+        self.try_setxy(x, y).unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_setxy(&self, x: f64, y: f64) -> Result<&TurtleRef, RuscapeError> {
+        self.check_alive()?;
+        self.move_with_ties((x, y), 0.)?;
+        Ok(self)
+    }
+    pub(crate) fn place(&self, x: f64, y: f64) {
         let current_patch = self.patch_here();
         self.borrow_mut().setxy(x, y);
         let next_patch = self.patch_here();
@@ -117,11 +131,13 @@ impl TurtleRef {
             current_patch.borrow_mut().remove_turtle_on(self);
             next_patch.borrow_mut().add_turtle_on(self)
         }
-        self
     }
     #[inline]
     pub fn fd(&self, distance: f64) -> &TurtleRef {
-        self.setxy(
+        self.try_fd(distance).unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_fd(&self, distance: f64) -> Result<&TurtleRef, RuscapeError> {
+        self.try_setxy(
             self.xcor() + distance * (90. - self.heading()).to_radians().cos(),
             self.ycor() + distance * (90. - self.heading()).to_radians().sin(),
         )
@@ -137,13 +153,19 @@ impl TurtleRef {
     }
     #[inline]
     pub fn left(&self, degree: f64) -> &TurtleRef {
-        self.borrow_mut().left(degree);
-        self
+        self.try_left(degree).unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_left(&self, degree: f64) -> Result<&TurtleRef, RuscapeError> {
+        self.try_right(-degree)
     }
     #[inline]
     pub fn right(&self, degree: f64) -> &TurtleRef {
-        self.borrow_mut().right(degree);
-        self
+        self.try_right(degree).unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_right(&self, degree: f64) -> Result<&TurtleRef, RuscapeError> {
+        self.check_alive()?;
+        self.move_with_ties((self.xcor(), self.ycor()), degree)?;
+        Ok(self)
     }
     #[inline]
     pub fn towardsxy(&self, x: f64, y: f64) -> f64 {
@@ -165,15 +187,16 @@ impl TurtleRef {
     }
     #[inline]
     pub fn move_in_radius(&self, distance: f64) -> &TurtleRef {
-        let r = distance * self.rng().f64().sqrt();
-        let theta = self.rng().f64() * std::f64::consts::PI * 2.;
+        let world = self.world();
+        let r = distance * world.borrow().rng().f64().sqrt();
+        let theta = world.borrow().rng().f64() * std::f64::consts::PI * 2.;
         self.setxy(self.xcor() + r * theta.cos(), self.ycor() + r * theta.sin());
         self
     }
     #[inline]
     pub fn random_headings(&self) -> &TurtleRef {
-        self.borrow_mut()
-            .set_heading(random_float(self.rng(), 0., 360.));
+        let heading = random_float(self.world().borrow().rng(), 0., 360.);
+        self.borrow_mut().set_heading(heading);
         self
     }
 }
@@ -208,41 +231,110 @@ impl TurtleRef {
     }
     #[inline]
     pub fn link_neighbors(&self) -> TurtleSet {
-        self.borrow().link_neighbors()
+        self.try_link_neighbors()
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_link_neighbors(&self) -> Result<TurtleSet, RuscapeError> {
+        self.check_alive()?;
+        Ok(self.borrow().link_neighbors())
     }
     #[inline]
     pub fn in_link_neighbors(&self) -> TurtleSet {
-        self.borrow().in_link_neighbors()
+        self.try_in_link_neighbors()
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_in_link_neighbors(&self) -> Result<TurtleSet, RuscapeError> {
+        self.check_alive()?;
+        Ok(self.borrow().in_link_neighbors())
     }
     #[inline]
     pub fn out_link_neighbors(&self) -> TurtleSet {
-        self.borrow().out_link_neighbors()
+        self.try_out_link_neighbors()
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_out_link_neighbors(&self) -> Result<TurtleSet, RuscapeError> {
+        self.check_alive()?;
+        Ok(self.borrow().out_link_neighbors())
+    }
+}
+
+impl TurtleRef {
+    #[inline]
+    pub fn is_link_neighbor(&self, other: &TurtleRef) -> bool {
+        self.is_alive() && other.is_alive() && self.borrow().is_link_neighbor(other)
+    }
+    #[inline]
+    pub fn is_in_link_neighbor(&self, other: &TurtleRef) -> bool {
+        self.is_alive() && other.is_alive() && self.borrow().is_in_link_neighbor(other)
+    }
+    #[inline]
+    pub fn is_out_link_neighbor(&self, other: &TurtleRef) -> bool {
+        self.is_alive() && other.is_alive() && self.borrow().is_out_link_neighbor(other)
+    }
+    #[inline]
+    pub fn link_with(&self, other: &TurtleRef) -> Option<LinkRef> {
+        self.check_alive().ok()?;
+        other.check_alive().ok()?;
+        self.borrow().link_with(other)
+    }
+    #[inline]
+    pub fn in_link_from(&self, other: &TurtleRef) -> Option<LinkRef> {
+        self.check_alive().ok()?;
+        other.check_alive().ok()?;
+        self.borrow().in_link_from(other)
+    }
+    #[inline]
+    pub fn out_link_to(&self, other: &TurtleRef) -> Option<LinkRef> {
+        self.check_alive().ok()?;
+        other.check_alive().ok()?;
+        self.borrow().out_link_to(other)
     }
 }
 
 impl TurtleRef {
     #[inline]
     pub fn create_link_with(&self, other: &TurtleRef) {
-        self.borrow().create_link_with(other)
+        self.try_create_link_with(other)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_create_link_with(&self, other: &TurtleRef) -> Result<(), RuscapeError> {
+        self.check_alive()?;
+        other.check_alive()?;
+        self.borrow().try_create_link_with(other)
     }
     #[inline]
-    pub fn create_link_from(self, other: &TurtleRef) {
-        self.borrow().create_link_from(other)
+    pub fn create_link_from(&self, other: &TurtleRef) {
+        self.try_create_link_from(other)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_create_link_from(&self, other: &TurtleRef) -> Result<(), RuscapeError> {
+        self.check_alive()?;
+        other.check_alive()?;
+        self.borrow().try_create_link_from(other)
     }
     #[inline]
     pub fn create_link_to(&self, other: &TurtleRef) {
-        self.borrow().create_link_to(other)
+        self.try_create_link_to(other)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_create_link_to(&self, other: &TurtleRef) -> Result<(), RuscapeError> {
+        self.check_alive()?;
+        other.check_alive()?;
+        self.borrow().try_create_link_to(other)
     }
     #[inline]
     pub fn create_links_with(&self, others: &mut TurtleSet) {
+        self.assert_alive();
         self.borrow().create_links_with(others)
     }
     #[inline]
     pub fn create_links_from(&self, others: &mut TurtleSet) {
+        self.assert_alive();
         self.borrow().create_links_from(others)
     }
     #[inline]
     pub fn create_links_to(&self, others: &mut TurtleSet) {
+        self.assert_alive();
         self.borrow().create_links_to(others)
     }
 }
@@ -250,35 +342,44 @@ impl TurtleRef {
 impl TurtleRef {
     #[inline]
     pub fn my_links(&self) -> LinkSet {
-        self.borrow().my_links()
+        self.try_my_links().unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_my_links(&self) -> Result<LinkSet, RuscapeError> {
+        self.check_alive()?;
+        Ok(self.borrow().my_links())
     }
     #[inline]
     pub fn my_in_links(&self) -> LinkSet {
-        self.borrow().my_in_links()
+        self.try_my_in_links().unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_my_in_links(&self) -> Result<LinkSet, RuscapeError> {
+        self.check_alive()?;
+        Ok(self.borrow().my_in_links())
     }
     #[inline]
     pub fn my_out_links(&self) -> LinkSet {
-        self.borrow().my_out_links()
+        self.try_my_out_links().unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_my_out_links(&self) -> Result<LinkSet, RuscapeError> {
+        self.check_alive()?;
+        Ok(self.borrow().my_out_links())
     }
 }
 
 impl TurtleRef {
     pub fn hatch(&self, amount: usize) -> TurtleSet {
-        let current_count = self.world().borrow().turtles().len();
-        let new_turtles = (current_count..amount + current_count)
+        self.try_hatch(amount).unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_hatch(&self, amount: usize) -> Result<TurtleSet, RuscapeError> {
+        self.check_alive()?;
+        let world = self.try_world()?;
+        let new_turtles = (0..amount)
             .map(|_| TurtleRef::new(self.borrow().clone()))
             .collect_vec();
-        new_turtles.iter().for_each(|t| {
-            let unid = self.world().borrow_mut().ug.add_node(self.clone());
-            t.borrow_mut().set_who(unid.index());
-            self.world()
-                .borrow_mut()
-                .turtles
-                .insert(unid.index(), t.clone());
-            let dnid = self.world().borrow_mut().dg.add_node(self.clone());
-            debug_assert_eq!(dnid, unid)
-        });
-        new_turtles.into()
+        new_turtles
+            .iter()
+            .for_each(|t| world.borrow_mut().add_turtle(t));
+        Ok(new_turtles.into())
     }
     #[inline]
     pub fn sprout(&self, amount: usize) -> TurtleSet {
@@ -286,22 +387,53 @@ impl TurtleRef {
     }
 }
 
+impl TurtleRef {
+    pub fn schedule<F: FnOnce(&TurtleRef) + 'static>(
+        &self,
+        time: f64,
+        priority: i64,
+        action: F,
+    ) -> Result<EventId, RuscapeError> {
+        self.check_alive()?;
+        let turtle = self.clone();
+        self.world().borrow_mut().schedule_event(
+            time,
+            priority,
+            Some(self.who()),
+            Box::new(move || action(&turtle)),
+        )
+    }
+}
+
 impl TurtleRef {
     pub fn die(&self) {
+        if !self.is_alive() {
+            return;
+        }
+        self.borrow_mut().set_dead();
         let who = self.who();
-        let nid = NodeIndex::new(who);
-        self.world().borrow_mut().turtles.delete(self);
-        self.world()
-            .borrow_mut()
-            .turtles
-            .values()
-            .last()
-            .unwrap()
-            .borrow_mut()
-            .set_who(who);
-        self.world().borrow_mut().ug.remove_node(nid);
-        self.world().borrow_mut().dg.remove_node(nid);
-        self.patch_here().borrow_mut().remove_turtle_on(self)
+        let nid = self.borrow().nid();
+        self.patch_here().borrow_mut().remove_turtle_on(self);
+        let world = self.world();
+        let mut world = world.borrow_mut();
+        world.events.cancel_owned_by(who);
+        world.turtles.delete(self);
+        world.unregister_breed(self);
+        let mut tied = 0;
+        world
+            .ug
+            .edges(nid)
+            .chain(world.dg.edges_directed(nid, Direction::Outgoing))
+            .chain(world.dg.edges_directed(nid, Direction::Incoming))
+            .for_each(|e| {
+                e.weight().borrow_mut().set_dead();
+                if e.weight().tie_mode() != TieMode::None {
+                    tied += 1;
+                }
+            });
+        world.tied_links -= tied;
+        world.ug.remove_node(nid);
+        world.dg.remove_node(nid);
     }
 }
 
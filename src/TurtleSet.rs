@@ -1,3 +1,4 @@
+use crate::Activation::Activation;
 use crate::GetRng::GetRng;
 use crate::Turtle::*;
 use crate::World::*;
@@ -11,6 +12,7 @@ use crate::TurtleRef::TurtleRef;
 use fastrand::Rng;
 
 use crate::common::concat;
+use crate::Error::RuscapeError;
 use crate::MapType::HashMap;
 use std::cell::RefCell;
 use std::fmt;
@@ -23,6 +25,7 @@ pub(crate) type TurtleCollection = HashMap<usize, TurtleRef>;
 pub struct TurtleSet {
     w: Option<WorldRef>,
     pub(crate) turtles: TurtleCollection,
+    pub(crate) activation: Option<Activation>,
 }
 impl Default for TurtleSet {
     fn default() -> Self {
@@ -91,6 +94,7 @@ impl TurtleSet {
         TurtleSet {
             w: None,
             turtles: v,
+            activation: None,
         }
     }
 }
@@ -98,12 +102,13 @@ impl TurtleSet {
 impl From<TurtleCollection> for TurtleSet {
     fn from(turtles: TurtleCollection) -> Self {
         if turtles.len() == 0 {
-            Default::default()
+            return Default::default();
         }
         let w = turtles.values().next().unwrap().borrow().world();
         TurtleSet {
             w: Some(WorldRef::new(&w)),
             turtles,
+            activation: None,
         }
     }
 }
@@ -127,14 +132,20 @@ impl From<Vec<TurtleRef>> for TurtleSet {
                 .iter()
                 .map(|t| (t.borrow().who(), t.clone()))
                 .collect::<TurtleCollection>(),
+            activation: None,
         }
     }
 }
 
 impl TurtleSet {
+    pub fn try_world(&self) -> Result<Rc<RefCell<World>>, RuscapeError> {
+        self.w
+            .as_ref()
+            .and_then(|w| w.upgrade())
+            .ok_or(RuscapeError::NoWorld)
+    }
     pub fn world(&self) -> Rc<RefCell<World>> {
-        let world = self.w.as_ref().unwrap().upgrade().unwrap().clone();
-        world
+        self.try_world().unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
@@ -146,16 +157,10 @@ impl GetRng for TurtleSet {
 
 impl Clone for TurtleSet {
     fn clone(&self) -> Self {
-        if self.len() == 0 {
-            TurtleSet {
-                w: None,
-                turtles: self.turtles.clone(),
-            }
-        } else {
-            TurtleSet {
-                w: Some(WorldRef::new(&self.world())),
-                turtles: self.turtles.clone(),
-            }
+        TurtleSet {
+            w: self.w.clone(),
+            turtles: self.turtles.clone(),
+            activation: self.activation,
         }
     }
 }
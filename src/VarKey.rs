@@ -0,0 +1,62 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Deref;
+use std::rc::Rc;
+
+// cloned keys share one allocation, and lookups by &str go through Borrow<str>
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VarKey(Rc<str>);
+
+impl VarKey {
+    pub fn new(name: &str) -> Self {
+        VarKey(Rc::from(name))
+    }
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for VarKey {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Borrow<str> for VarKey {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for VarKey {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for VarKey {
+    fn from(name: &str) -> Self {
+        VarKey::new(name)
+    }
+}
+
+impl From<String> for VarKey {
+    fn from(name: String) -> Self {
+        VarKey(Rc::from(name))
+    }
+}
+
+impl Debug for VarKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&*self.0, f)
+    }
+}
+
+impl Display for VarKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&*self.0, f)
+    }
+}
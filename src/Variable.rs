@@ -1,30 +1,332 @@
-#[derive(Debug, Clone, PartialEq)]
+use crate::Agent::Agent;
+use crate::AgentSet::AgentSet;
+use crate::Error::RuscapeError;
+use crate::LinkRef::LinkRef;
+use crate::LinkSet::LinkSet;
+use crate::PatchRef::PatchRef;
+use crate::PatchSet::PatchSet;
+use crate::TurtleRef::TurtleRef;
+use crate::TurtleSet::TurtleSet;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+#[derive(Debug, Clone)]
 pub enum Variable {
     Boolean(bool),
     Float(f64),
     Integer(i64),
     String(String),
+    List(Vec<Variable>),
+    Map(BTreeMap<String, Variable>),
+    Nobody,
+    Turtle(TurtleRef),
+    Patch(PatchRef),
+    Link(LinkRef),
+    TurtleSet(TurtleSet),
+    PatchSet(PatchSet),
+    LinkSet(LinkSet),
+}
+
+impl PartialEq for Variable {
+    fn eq(&self, other: &Self) -> bool {
+        if self.is_nobody() || other.is_nobody() {
+            return self.is_nobody() && other.is_nobody();
+        }
+        match (self, other) {
+            (Variable::Boolean(a), Variable::Boolean(b)) => a == b,
+            (Variable::Float(a), Variable::Float(b)) => a == b,
+            (Variable::Integer(a), Variable::Integer(b)) => a == b,
+            (Variable::String(a), Variable::String(b)) => a == b,
+            (Variable::List(a), Variable::List(b)) => a == b,
+            (Variable::Map(a), Variable::Map(b)) => a == b,
+            (Variable::Turtle(a), Variable::Turtle(b)) => a == b,
+            (Variable::Patch(a), Variable::Patch(b)) => a == b,
+            (Variable::Link(a), Variable::Link(b)) => a == b,
+            (Variable::TurtleSet(a), Variable::TurtleSet(b)) => same_members(a, b),
+            (Variable::PatchSet(a), Variable::PatchSet(b)) => same_members(a, b),
+            (Variable::LinkSet(a), Variable::LinkSet(b)) => same_members(a, b),
+            _ => match (self.as_f64(), other.as_f64()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl PartialOrd for Variable {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self == other {
+            return Some(Ordering::Equal);
+        }
+        match (self, other) {
+            (Variable::Integer(a), Variable::Integer(b)) => a.partial_cmp(b),
+            (Variable::Boolean(a), Variable::Boolean(b)) => a.partial_cmp(b),
+            (Variable::String(a), Variable::String(b)) => a.partial_cmp(b),
+            (Variable::List(a), Variable::List(b)) => a.partial_cmp(b),
+            (Variable::Turtle(a), Variable::Turtle(b)) => a.partial_cmp(b),
+            (Variable::Patch(a), Variable::Patch(b)) => a.partial_cmp(b),
+            (Variable::Link(a), Variable::Link(b)) => a.partial_cmp(b),
+            _ => match (self.as_f64(), other.as_f64()) {
+                (Some(a), Some(b)) => a.partial_cmp(&b),
+                _ => None,
+            },
+        }
+    }
+}
+
+fn same_members<A, AS>(a: &AS, b: &AS) -> bool
+where
+    A: Agent,
+    A::IDX: Eq + std::hash::Hash + Copy + std::fmt::Debug,
+    AS: AgentSet<A>,
+{
+    a.count() == b.count() && a.all(|agent| b.get(&agent.borrow().who()).is_some())
 }
 
 macro_rules! impl_eq {
-    ($dest:ident,$src:ty) => {
+    ($src:ty) => {
+        impl PartialEq<$src> for Variable {
+            fn eq(&self, other: &$src) -> bool {
+                self.partial_cmp(other) == Some(Ordering::Equal)
+            }
+        }
+
+        impl PartialOrd<$src> for Variable {
+            fn partial_cmp(&self, other: &$src) -> Option<Ordering> {
+                self.partial_cmp(&Variable::from(*other))
+            }
+        }
+    };
+}
+
+macro_rules! impl_str_eq {
+    ($src:ty) => {
         impl PartialEq<$src> for Variable {
             fn eq(&self, other: &$src) -> bool {
-                if let Self::$dest(item) = self {
-                    item == other
-                } else {
-                    panic!("eq not implemented")
+                matches!(self, Variable::String(s) if s.as_str() == AsRef::<str>::as_ref(other))
+            }
+        }
+
+        impl PartialOrd<$src> for Variable {
+            fn partial_cmp(&self, other: &$src) -> Option<Ordering> {
+                match self {
+                    Variable::String(s) => s.as_str().partial_cmp(AsRef::<str>::as_ref(other)),
+                    _ => None,
                 }
             }
         }
     };
 }
 
-impl_eq!(Boolean, bool);
-impl_eq!(Float, f64);
-impl_eq!(Integer, i64);
-impl_eq!(String, String);
-impl_eq!(String, &'static str);
+macro_rules! impl_op {
+    ($op:ident, $method:ident, $assign_op:ident, $assign_method:ident, $try_method:ident) => {
+        impl<T: Into<Variable>> $op<T> for Variable {
+            type Output = Variable;
+
+            fn $method(self, rhs: T) -> Self::Output {
+                self.$try_method(&rhs.into())
+                    .unwrap_or_else(|e| panic!("{}", e))
+            }
+        }
+
+        impl<T: Into<Variable>> $assign_op<T> for Variable {
+            fn $assign_method(&mut self, rhs: T) {
+                *self = self
+                    .$try_method(&rhs.into())
+                    .unwrap_or_else(|e| panic!("{}", e));
+            }
+        }
+
+        impl $op<Variable> for f64 {
+            type Output = Variable;
+
+            fn $method(self, rhs: Variable) -> Self::Output {
+                Variable::from(self).$method(rhs)
+            }
+        }
+
+        impl $op<Variable> for i64 {
+            type Output = Variable;
+
+            fn $method(self, rhs: Variable) -> Self::Output {
+                Variable::from(self).$method(rhs)
+            }
+        }
+    };
+}
+
+macro_rules! impl_try_from {
+    ($dest:ty, $var_type:ident, $pattern:pat => $value:expr) => {
+        impl TryFrom<Variable> for $dest {
+            type Error = RuscapeError;
+
+            fn try_from(variable: Variable) -> Result<Self, Self::Error> {
+                match variable {
+                    $pattern => Ok($value),
+                    other => Err(RuscapeError::WrongType {
+                        expected: VarType::$var_type,
+                        found: other.var_type(),
+                    }),
+                }
+            }
+        }
+    };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VarType {
+    Boolean,
+    Float,
+    Integer,
+    String,
+    List,
+    Map,
+    Nobody,
+    Turtle,
+    Patch,
+    Link,
+    TurtleSet,
+    PatchSet,
+    LinkSet,
+}
+
+impl VarType {
+    pub fn accepts(&self, value: &Variable) -> bool {
+        match value.var_type() {
+            VarType::Nobody => matches!(
+                self,
+                VarType::Nobody | VarType::Turtle | VarType::Patch | VarType::Link
+            ),
+            var_type => var_type == *self,
+        }
+    }
+}
+
+impl Variable {
+    pub fn var_type(&self) -> VarType {
+        match self {
+            Variable::Boolean(_) => VarType::Boolean,
+            Variable::Float(_) => VarType::Float,
+            Variable::Integer(_) => VarType::Integer,
+            Variable::String(_) => VarType::String,
+            Variable::List(_) => VarType::List,
+            Variable::Map(_) => VarType::Map,
+            Variable::Nobody => VarType::Nobody,
+            Variable::Turtle(_) => VarType::Turtle,
+            Variable::Patch(_) => VarType::Patch,
+            Variable::Link(_) => VarType::Link,
+            Variable::TurtleSet(_) => VarType::TurtleSet,
+            Variable::PatchSet(_) => VarType::PatchSet,
+            Variable::LinkSet(_) => VarType::LinkSet,
+        }
+    }
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Variable::Float(num) => Some(*num),
+            Variable::Integer(num) => Some(*num as f64),
+            _ => None,
+        }
+    }
+    fn arithmetic(
+        &self,
+        rhs: &Variable,
+        op: &'static str,
+        int: Option<fn(i64, i64) -> Option<i64>>,
+        float: fn(f64, f64) -> f64,
+    ) -> Result<Variable, RuscapeError> {
+        match (self, rhs, int) {
+            (Variable::Integer(a), Variable::Integer(b), Some(int)) => int(*a, *b)
+                .map(Variable::Integer)
+                .ok_or(RuscapeError::IntegerOverflow {
+                    op,
+                    left: *a,
+                    right: *b,
+                }),
+            _ => match (self.as_f64(), rhs.as_f64()) {
+                (Some(a), Some(b)) => Ok(Variable::Float(float(a, b))),
+                _ => Err(RuscapeError::InvalidOperation {
+                    op,
+                    left: self.var_type(),
+                    right: rhs.var_type(),
+                }),
+            },
+        }
+    }
+    pub fn try_add(&self, rhs: &Variable) -> Result<Variable, RuscapeError> {
+        self.arithmetic(rhs, "+", Some(i64::checked_add), |a, b| a + b)
+    }
+    pub fn try_sub(&self, rhs: &Variable) -> Result<Variable, RuscapeError> {
+        self.arithmetic(rhs, "-", Some(i64::checked_sub), |a, b| a - b)
+    }
+    pub fn try_mul(&self, rhs: &Variable) -> Result<Variable, RuscapeError> {
+        self.arithmetic(rhs, "*", Some(i64::checked_mul), |a, b| a * b)
+    }
+    pub fn try_div(&self, rhs: &Variable) -> Result<Variable, RuscapeError> {
+        self.arithmetic(rhs, "/", None, |a, b| a / b)
+    }
+    pub fn try_neg(&self) -> Result<Variable, RuscapeError> {
+        match self {
+            Variable::Integer(num) => {
+                num.checked_neg()
+                    .map(Variable::Integer)
+                    .ok_or(RuscapeError::IntegerOverflow {
+                        op: "-",
+                        left: 0,
+                        right: *num,
+                    })
+            }
+            Variable::Float(num) => Ok(Variable::Float(-num)),
+            other => Err(RuscapeError::InvalidUnaryOperation {
+                op: "-",
+                operand: other.var_type(),
+            }),
+        }
+    }
+    pub fn is_nobody(&self) -> bool {
+        match self {
+            Variable::Nobody => true,
+            Variable::Turtle(turtle) => !turtle.is_alive(),
+            Variable::Link(link) => !link.is_alive(),
+            _ => false,
+        }
+    }
+}
+
+impl_eq!(bool);
+impl_eq!(f64);
+impl_eq!(i64);
+impl_str_eq!(String);
+impl_str_eq!(&'static str);
+
+impl_op!(Add, add, AddAssign, add_assign, try_add);
+impl_op!(Sub, sub, SubAssign, sub_assign, try_sub);
+impl_op!(Mul, mul, MulAssign, mul_assign, try_mul);
+impl_op!(Div, div, DivAssign, div_assign, try_div);
+
+impl Neg for Variable {
+    type Output = Variable;
+
+    fn neg(self) -> Self::Output {
+        self.try_neg().unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl TryFrom<Variable> for f64 {
+    type Error = RuscapeError;
+
+    fn try_from(variable: Variable) -> Result<Self, Self::Error> {
+        variable.as_f64().ok_or(RuscapeError::WrongType {
+            expected: VarType::Float,
+            found: variable.var_type(),
+        })
+    }
+}
+
+impl_try_from!(i64, Integer, Variable::Integer(num) => num);
+impl_try_from!(bool, Boolean, Variable::Boolean(b) => b);
+impl_try_from!(String, String, Variable::String(s) => s);
 
 impl Default for Variable {
     fn default() -> Self {
@@ -61,3 +363,110 @@ impl From<String> for Variable {
         Self::String(s)
     }
 }
+
+impl<T: Into<Variable>> From<Vec<T>> for Variable {
+    fn from(items: Vec<T>) -> Self {
+        Self::List(items.into_iter().map(Into::into).collect())
+    }
+}
+
+impl From<BTreeMap<String, Variable>> for Variable {
+    fn from(map: BTreeMap<String, Variable>) -> Self {
+        Self::Map(map)
+    }
+}
+
+impl<T: Into<Variable>> From<Option<T>> for Variable {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Nobody, Into::into)
+    }
+}
+
+impl From<TurtleRef> for Variable {
+    fn from(turtle: TurtleRef) -> Self {
+        Self::Turtle(turtle)
+    }
+}
+
+impl From<PatchRef> for Variable {
+    fn from(patch: PatchRef) -> Self {
+        Self::Patch(patch)
+    }
+}
+
+impl From<LinkRef> for Variable {
+    fn from(link: LinkRef) -> Self {
+        Self::Link(link)
+    }
+}
+
+impl From<TurtleSet> for Variable {
+    fn from(turtles: TurtleSet) -> Self {
+        Self::TurtleSet(turtles)
+    }
+}
+
+impl From<PatchSet> for Variable {
+    fn from(patches: PatchSet) -> Self {
+        Self::PatchSet(patches)
+    }
+}
+
+impl From<LinkSet> for Variable {
+    fn from(links: LinkSet) -> Self {
+        Self::LinkSet(links)
+    }
+}
+
+macro_rules! impl_integer {
+    ($($src:ty),*) => {
+        $(
+            impl TryFrom<Variable> for $src {
+                type Error = RuscapeError;
+
+                fn try_from(variable: Variable) -> Result<Self, Self::Error> {
+                    let num = i64::try_from(variable)?;
+                    <$src>::try_from(num).map_err(|_| RuscapeError::IntegerOutOfRange(num.into()))
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i32, u32, usize);
+
+impl From<i32> for Variable {
+    fn from(num: i32) -> Self {
+        Self::Integer(num.into())
+    }
+}
+
+impl From<u32> for Variable {
+    fn from(num: u32) -> Self {
+        Self::Integer(num.into())
+    }
+}
+
+impl TryFrom<usize> for Variable {
+    type Error = RuscapeError;
+
+    fn try_from(num: usize) -> Result<Self, Self::Error> {
+        i64::try_from(num)
+            .map(Self::Integer)
+            .map_err(|_| RuscapeError::IntegerOutOfRange(num as i128))
+    }
+}
+
+impl TryFrom<Variable> for f32 {
+    type Error = RuscapeError;
+
+    fn try_from(variable: Variable) -> Result<Self, Self::Error> {
+        f64::try_from(variable).map(|num| num as f32)
+    }
+}
+
+impl From<f32> for Variable {
+    fn from(num: f32) -> Self {
+        Self::Float(num as f64)
+    }
+}
@@ -1,15 +1,24 @@
-use crate::GetRng::GetRng;
+use crate::Activation::Activation;
+use crate::Agent::Agent;
+use crate::Breed::Breeds;
+use crate::Error::RuscapeError;
+use crate::Event::EventQueue;
+use crate::GetRng::{GetRng, RngState};
+use crate::Link::Link;
 use crate::LinkRef::LinkRef;
+use crate::LinkSet::LinkSet;
 use crate::PatchSet::{OriginLocation, PatchSet};
 use crate::TurtleRef::TurtleRef;
 use crate::TurtleSet::*;
 use fastrand::*;
+use itertools::Itertools;
 
-use petgraph::graph::{DiGraph, UnGraph};
-use petgraph_gen::classic::*;
+use petgraph::graph::NodeIndex;
+use petgraph::stable_graph::{StableDiGraph, StableUnGraph};
+use petgraph::visit::IntoEdgeReferences;
+use petgraph::Direction;
 use std::cell::RefCell;
 
-use crate::Agent::Agent;
 use crate::AgentSet::AgentSet;
 
 use crate::PatchRef::PatchRef;
@@ -29,11 +38,18 @@ pub struct World {
     pub(crate) x_max: i64,
     pub(crate) y_min: i64,
     pub(crate) y_max: i64,
+    seed: u64,
     rng: Rng,
+    activation: Activation,
+    pub(crate) ticks: Option<f64>,
+    pub(crate) events: EventQueue,
     pub(crate) turtles: TurtleSet,
-    pub(crate) ug: UnGraph<TurtleRef, LinkRef>,
-    pub(crate) dg: DiGraph<TurtleRef, LinkRef>,
+    next_who: usize,
+    pub(crate) ug: StableUnGraph<TurtleRef, LinkRef>,
+    pub(crate) dg: StableDiGraph<TurtleRef, LinkRef>,
     patches: PatchSet,
+    pub(crate) breeds: Breeds,
+    pub(crate) tied_links: usize,
 }
 
 impl World {
@@ -46,6 +62,13 @@ impl World {
     pub fn turtles(&self) -> TurtleSet {
         self.turtles.clone()
     }
+    pub fn links(&self) -> LinkSet {
+        let mut links: LinkSet = self.all_links().cloned().collect_vec().into();
+        if links.w().is_none() {
+            links.set_w(Some(WorldRef::new(&self.turtles.world())));
+        }
+        links
+    }
     pub fn patches(&self) -> PatchSet {
         self.patches.clone()
     }
@@ -55,7 +78,48 @@ impl World {
         self.patches[(pycor + pxcor * (self.y_max + 1 - self.y_min)) as usize].clone()
     }
     pub fn turtle(&self, who: usize) -> TurtleRef {
-        self.turtles.index(who)
+        self.try_turtle(who).unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_turtle(&self, who: usize) -> Result<TurtleRef, RuscapeError> {
+        self.turtles
+            .get(&who)
+            .cloned()
+            .ok_or(RuscapeError::NoSuchTurtle(who))
+    }
+}
+
+impl World {
+    pub fn activation(&self) -> Activation {
+        self.activation
+    }
+    pub fn set_activation(&mut self, activation: Activation) {
+        self.activation = activation;
+    }
+}
+
+impl World {
+    pub fn ticks(&self) -> Result<f64, RuscapeError> {
+        self.ticks.ok_or(RuscapeError::TicksNotStarted)
+    }
+    pub fn reset_ticks(&mut self) {
+        self.ticks = Some(0.);
+    }
+    pub fn clear_ticks(&mut self) {
+        self.ticks = None;
+    }
+    pub fn tick(&mut self) -> Result<f64, RuscapeError> {
+        self.tick_advance(1.)
+    }
+    pub fn tick_advance(&mut self, amount: f64) -> Result<f64, RuscapeError> {
+        if amount < 0. {
+            return Err(RuscapeError::NegativeTickAdvance(amount));
+        }
+        if !amount.is_finite() {
+            return Err(RuscapeError::EventInPast(amount));
+        }
+        let ticks = self.ticks()? + amount;
+        self.ticks = Some(ticks);
+        Ok(ticks)
     }
 }
 
@@ -66,6 +130,7 @@ impl Debug for World {
             .field("max_pycor", &self.max_pycor)
             .field("origin_location", &self.origin_location)
             .field("is_periodic", &self.is_periodic)
+            .field("ticks", &self.ticks)
             .finish()
     }
 }
@@ -74,6 +139,55 @@ impl World {
     pub fn rng(&self) -> &Rng {
         &self.rng
     }
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+    pub fn random_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng.seed(seed);
+        self.seed_agent_rngs();
+    }
+    pub fn rng_state(&self) -> RngState {
+        RngState {
+            seed: self.seed,
+            world: self.rng.get_seed(),
+            turtles: self
+                .turtles
+                .iter()
+                .map(|(&who, t)| (who, t.rng_state()))
+                .collect(),
+            patches: self
+                .patches
+                .iter()
+                .map(|(&who, p)| (who, p.rng_state()))
+                .collect(),
+            links: self.all_links().map(|l| (l.id(), l.rng_state())).collect(),
+        }
+    }
+    pub fn set_rng_state(&mut self, state: &RngState) {
+        self.seed = state.seed;
+        self.rng.seed(state.world);
+        state.turtles.iter().for_each(|(who, &s)| {
+            if let Some(t) = self.turtles.get(who) {
+                t.set_rng_state(s)
+            }
+        });
+        state.patches.iter().for_each(|(who, &s)| {
+            if let Some(p) = self.patches.get(who) {
+                p.set_rng_state(s)
+            }
+        });
+        self.all_links().for_each(|l| {
+            if let Some(&s) = state.links.get(&l.id()) {
+                l.set_rng_state(s)
+            }
+        });
+    }
+    fn seed_agent_rngs(&self) {
+        self.turtles.values().for_each(|t| t.seed_rng(self.seed));
+        self.patches.values().for_each(|p| p.seed_rng(self.seed));
+        self.all_links().for_each(|l| l.seed_link_rng(self.seed));
+    }
 }
 
 impl World {
@@ -83,13 +197,14 @@ impl World {
         max_pycor: i64,
         origin_location: OriginLocation,
         is_periodic: bool,
+        seed: u64,
     ) -> Rc<RefCell<World>> {
         let (x_min, x_max, y_min, y_max) = match origin_location {
             OriginLocation::Center => (-max_pxcor, max_pxcor, -max_pycor, max_pycor),
             OriginLocation::Corner => (0, max_pxcor, 0, max_pycor),
         };
-        let rng = Rng::new();
-        let mut turtles = TurtleSet::new(
+        let rng = Rng::with_seed(seed);
+        let turtles = TurtleSet::new(
             amount,
             max_pxcor,
             max_pycor,
@@ -99,26 +214,14 @@ impl World {
             y_min as f64 - 0.5,
             y_max as f64 + 0.5,
         );
-        let ug = {
-            let mut g = empty_graph::<TurtleRef, LinkRef>(0);
-            turtles.values().for_each(|turtle| {
-                turtle
-                    .borrow_mut()
-                    .set_who(g.add_node(turtle.clone()).index());
-            });
-            g
-        };
-        let dg = {
-            let mut g = empty_digraph::<TurtleRef, LinkRef>(0);
-            turtles.values().for_each(|turtle| {
-                let nid = g.add_node(turtle.clone());
-                debug_assert_eq!(nid.index(), turtle.borrow().who());
-            });
-            g
-        };
-        turtles.clear();
-        ug.node_indices().for_each(|nid| {
-            turtles.insert(nid.index(), ug.node_weight(nid).unwrap().clone());
+        let mut ug = StableUnGraph::<TurtleRef, LinkRef>::default();
+        let mut dg = StableDiGraph::<TurtleRef, LinkRef>::default();
+        turtles.iter().for_each(|(&who, turtle)| {
+            let unid = ug.add_node(turtle.clone());
+            let dnid = dg.add_node(turtle.clone());
+            debug_assert_eq!(unid, dnid);
+            turtle.borrow_mut().set_who(who);
+            turtle.borrow_mut().set_nid(unid);
         });
         let patches = PatchSet::new(max_pxcor, max_pycor, origin_location, is_periodic);
 
@@ -131,11 +234,18 @@ impl World {
             x_max,
             y_min,
             y_max,
+            seed,
             rng,
+            activation: Default::default(),
+            ticks: None,
+            events: Default::default(),
             turtles,
+            next_who: amount,
             ug,
             dg,
             patches,
+            breeds: Default::default(),
+            tied_links: 0,
         }))
     }
 
@@ -146,8 +256,34 @@ impl World {
         origin_location: OriginLocation,
         is_periodic: bool,
     ) -> Rc<RefCell<World>> {
-        let world_ref = Self::new(amount, max_pxcor, max_pycor, origin_location, is_periodic);
+        Self::init_with_seed(
+            amount,
+            max_pxcor,
+            max_pycor,
+            origin_location,
+            is_periodic,
+            fastrand::u64(..),
+        )
+    }
+
+    pub fn init_with_seed(
+        amount: usize,
+        max_pxcor: i64,
+        max_pycor: i64,
+        origin_location: OriginLocation,
+        is_periodic: bool,
+        seed: u64,
+    ) -> Rc<RefCell<World>> {
+        let world_ref = Self::new(
+            amount,
+            max_pxcor,
+            max_pycor,
+            origin_location,
+            is_periodic,
+            seed,
+        );
         world_ref.as_ref().borrow_mut().register_world(&world_ref);
+        world_ref.borrow().seed_agent_rngs();
         world_ref
             .borrow()
             .turtles
@@ -182,6 +318,28 @@ impl World {
     }
 }
 
+// turtles, patches and links may refer to each other through their variables, so those
+// references are dropped with the world instead of leaking as Rc cycles
+impl Drop for World {
+    fn drop(&mut self) {
+        self.turtles.turtles().values().for_each(|t| {
+            if let Ok(mut t) = t.try_borrow_mut() {
+                t.clear_variables();
+            }
+        });
+        self.patches.values().for_each(|p| {
+            if let Ok(mut p) = p.try_borrow_mut() {
+                p.clear_variables();
+            }
+        });
+        self.all_links().for_each(|l| {
+            if let Ok(mut l) = l.try_borrow_mut() {
+                l.clear_variables();
+            }
+        });
+    }
+}
+
 pub struct WorldRef {
     world_ref: Weak<RefCell<World>>,
 }
@@ -218,38 +376,121 @@ impl Clone for WorldRef {
 
 impl World {
     pub fn crt(&mut self, amount: usize) -> TurtleSet {
-        let current_count = self.turtles.len();
-        let new_turtles: TurtleSet = (current_count..(amount + current_count))
-            .map(|who| {
-                (who, {
-                    let t = TurtleRef::new(Turtle::new(
-                        self.max_pxcor,
-                        self.max_pycor,
-                        self.is_periodic(),
-                        self.x_min as f64 - 0.5,
-                        self.x_max as f64 + 0.5,
-                        self.y_min as f64 - 0.5,
-                        self.y_max as f64 + 0.5,
-                    ));
-                    t.borrow_mut()
-                        .set_world(&WorldRef::new(&self.turtles.world()));
-                    t
-                })
+        (0..amount)
+            .map(|_| {
+                let t = TurtleRef::new(Turtle::new(
+                    self.max_pxcor,
+                    self.max_pycor,
+                    self.is_periodic(),
+                    self.x_min as f64 - 0.5,
+                    self.x_max as f64 + 0.5,
+                    self.y_min as f64 - 0.5,
+                    self.y_max as f64 + 0.5,
+                ));
+                t.borrow_mut()
+                    .set_world(&WorldRef::new(&self.turtles.world()));
+                self.add_turtle(&t);
+                (t.who(), t)
             })
             .collect::<TurtleCollection>()
-            .into();
-        new_turtles.values().for_each(|t| {
-            let unid = self.ug.add_node(t.clone());
-            let dnid = self.dg.add_node(t.clone());
-            debug_assert_eq!(unid, dnid);
-            t.borrow_mut().set_who(unid.index());
-        });
-        self.turtles.extends(&new_turtles);
-        new_turtles
+            .into()
+    }
+
+    pub(crate) fn add_turtle(&mut self, t: &TurtleRef) {
+        let who = self.next_who;
+        self.next_who += 1;
+        let unid = self.ug.add_node(t.clone());
+        let dnid = self.dg.add_node(t.clone());
+        debug_assert_eq!(unid, dnid);
+        t.borrow_mut().set_who(who);
+        t.borrow_mut().set_nid(unid);
+        t.seed_rng(self.seed);
+        self.turtles.append(t);
+        self.register_breed(t);
+        self.patch(t.xcor(), t.ycor()).borrow_mut().add_turtle_on(t);
+    }
+
+    pub(crate) fn add_link(
+        &mut self,
+        nid1: NodeIndex,
+        nid2: NodeIndex,
+        directed: bool,
+        breed: Option<&str>,
+    ) -> Result<LinkRef, RuscapeError> {
+        let (who1, who2) = (self.ug[nid1].who(), self.ug[nid2].who());
+        if nid1 == nid2 {
+            return Err(RuscapeError::SelfLink(who1));
+        }
+        let (end1, end2) = if !directed && who2 < who1 {
+            ((who2, nid2), (who1, nid1))
+        } else {
+            ((who1, nid1), (who2, nid2))
+        };
+        if let Some(link) = self.find_link(end1.1, end2.1, directed, breed) {
+            let same = {
+                let link = link.borrow();
+                link.is_directed() == directed && link.nids() == (end1.1, end2.1)
+            };
+            return if same {
+                Ok(link)
+            } else {
+                Err(RuscapeError::LinkExists(end1.0, end2.0))
+            };
+        }
+        let breed = breed.map(|name| (name, self.link_breed_id(name)));
+        let link = LinkRef::new(Link::new(
+            &self.turtles.world(),
+            end1,
+            end2,
+            directed,
+            breed,
+        ));
+        let eid = if directed {
+            self.dg.add_edge(end1.1, end2.1, link.clone())
+        } else {
+            self.ug.add_edge(end1.1, end2.1, link.clone())
+        };
+        link.borrow_mut().set_eid(eid);
+        link.seed_link_rng(self.seed);
+        Ok(link)
+    }
+
+    // like NetLogo, a pair of turtles has at most one link of each breed, except that a
+    // directed breed may link them once in each direction
+    pub(crate) fn find_link(
+        &self,
+        nid1: NodeIndex,
+        nid2: NodeIndex,
+        directed: bool,
+        breed: Option<&str>,
+    ) -> Option<LinkRef> {
+        self.ug
+            .edges(nid1)
+            .chain(self.dg.edges_directed(nid1, Direction::Outgoing))
+            .chain(self.dg.edges_directed(nid1, Direction::Incoming))
+            .map(|e| e.weight())
+            .find(|link| {
+                let link = link.borrow();
+                let (a, b) = link.nids();
+                let joins = (a, b) == (nid1, nid2) || (a, b) == (nid2, nid1);
+                let clashes = !directed || !link.is_directed() || (a, b) == (nid1, nid2);
+                joins && link.breed() == breed && clashes
+            })
+            .cloned()
+    }
+
+    pub(crate) fn all_links(&self) -> impl Iterator<Item = &LinkRef> {
+        self.ug
+            .edge_references()
+            .map(|e| e.weight())
+            .chain(self.dg.edge_references().map(|e| e.weight()))
     }
 
     pub fn clear_links(&mut self) {
+        self.all_links()
+            .for_each(|link| link.borrow_mut().set_dead());
         self.ug.clear_edges();
         self.dg.clear_edges();
+        self.tied_links = 0;
     }
 }
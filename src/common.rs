@@ -17,7 +17,7 @@ where
 }
 
 #[inline]
-pub fn one_of<A, AS>(agent_set: &AS) -> AgentRef<A>
+pub fn one_of<A, AS>(agent_set: &AS) -> Option<AgentRef<A>>
 where
     A: Agent,
     A::IDX: Eq + Hash + Copy + Debug,
@@ -36,7 +36,7 @@ where
     agent_set.n_of_with_repeats(amount)
 }
 #[inline]
-pub fn one_of_weighted_by<A, K, F, AS>(agent_set: AS, key: F) -> AgentRef<A>
+pub fn one_of_weighted_by<A, K, F, AS>(agent_set: AS, key: F) -> Option<AgentRef<A>>
 where
     A: Agent,
     A::IDX: Eq + Hash + Copy + Debug,
@@ -66,7 +66,7 @@ where
     agent_set.n_of_weighted_by(amount, key)
 }
 #[inline]
-pub fn max_one_of<A, T, F, AS>(agents: AS, key: F) -> AgentRef<A>
+pub fn max_one_of<A, T, F, AS>(agents: AS, key: F) -> Option<AgentRef<A>>
 where
     A: Agent,
     A::IDX: Eq + Hash + Copy + Debug,
@@ -77,7 +77,7 @@ where
     agents.max_one_of(key)
 }
 #[inline]
-pub fn min_one_of<A, T, F, AS>(agents: AS, key: F) -> AgentRef<A>
+pub fn min_one_of<A, T, F, AS>(agents: AS, key: F) -> Option<AgentRef<A>>
 where
     A: Agent,
     A::IDX: Eq + Hash + Copy + Debug,
@@ -1,22 +1,34 @@
 #![allow(non_snake_case)]
 #![feature(trait_alias)]
+mod Activation;
 mod Agent;
 mod AgentSet;
+mod AgentVars;
 mod AsSlice;
+mod Breed;
+mod Centrality;
+mod Commands;
+mod Error;
+mod Event;
 mod GetRng;
+mod Gillespie;
 mod Link;
 mod LinkRef;
 mod LinkSet;
 mod MapType;
 mod Model;
+mod Network;
 mod Patch;
 mod PatchRef;
 mod PatchSet;
 mod Position;
+mod Random;
+mod Tie;
 mod Toroidal;
 mod Turtle;
 mod TurtleRef;
 mod TurtleSet;
+mod VarKey;
 mod Variable;
 mod World;
 mod common;
@@ -25,7 +37,25 @@ pub mod prelude;
 #[macro_export]
 macro_rules! init {
     ($turtle_count:literal, $max_pxcor:literal, $max_pycor:literal, $origin:ident, $is_periodic:literal) => {
-        let w = World::init($turtle_count, $max_pxcor, $max_pycor, $origin, $is_periodic);
+        $crate::init!(
+            $turtle_count,
+            $max_pxcor,
+            $max_pycor,
+            $origin,
+            $is_periodic,
+            $crate::prelude::fastrand::u64(..)
+        );
+    };
+    ($turtle_count:literal, $max_pxcor:literal, $max_pycor:literal, $origin:ident, $is_periodic:literal, $seed:expr) => {
+        let w = World::init_with_seed(
+            $turtle_count,
+            $max_pxcor,
+            $max_pycor,
+            $origin,
+            $is_periodic,
+            $seed,
+        );
+        let rng = w.borrow().rng().clone();
         let tsw = w.clone();
         let tw = w.clone();
         let psw = w.clone();
@@ -34,7 +64,6 @@ macro_rules! init {
         let turtle = move |who: usize| tw.borrow().turtle(who);
         let patch = move |x, y| psw.borrow().patch(x, y);
 
-        let rng = fastrand::Rng::new();
         let random = move |high: i64| rng.i64(0..high);
     };
 }
@@ -42,6 +71,9 @@ macro_rules! init {
 #[macro_export]
 macro_rules! model {
     (name = $model_name:ident,exporter = $data_name:ident, turtle_count = $turtle_count:literal, max_pxcor = $max_pxcor:literal, max_pycor = $max_pycor:literal, origin = $origin:ident, is_periodic = $is_periodic:literal, $(const $name:ident:$t:ty = $l:literal),* ,$setup:item, $go:item) => {
+        $crate::model!(name = $model_name, exporter = $data_name, turtle_count = $turtle_count, max_pxcor = $max_pxcor, max_pycor = $max_pycor, origin = $origin, is_periodic = $is_periodic, seed = $crate::prelude::fastrand::u64(..), $(const $name:$t = $l),* , $setup, $go);
+    };
+    (name = $model_name:ident,exporter = $data_name:ident, turtle_count = $turtle_count:literal, max_pxcor = $max_pxcor:literal, max_pycor = $max_pycor:literal, origin = $origin:ident, is_periodic = $is_periodic:literal, seed = $seed:expr, $(const $name:ident:$t:ty = $l:literal),* ,$setup:item, $go:item) => {
         mod $model_name{
         use serde::{Deserialize, Serialize};
         use std::cell::RefCell;
@@ -57,12 +89,13 @@ macro_rules! model {
         impl RuscapeModel {
             pub fn new() -> Self {
                 RuscapeModel {
-                    world: World::init(
+                    world: World::init_with_seed(
                         $turtle_count,
                         $max_pxcor,
                         $max_pycor,
                         $origin,
                         $is_periodic,
+                        $seed,
                     ),
                 }
             }
@@ -73,6 +106,10 @@ macro_rules! model {
             pub fn turtles(&self) -> TurtleSet {
                 self.world.borrow().turtles()
             }
+
+            pub fn ticks(&self) -> Result<f64, RuscapeError> {
+                self.world.borrow().ticks()
+            }
         }
 
         impl Model<$data_name> for RuscapeModel {
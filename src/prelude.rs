@@ -1,10 +1,27 @@
 pub use crate::common::*;
+pub use crate::Activation::{Activation, HasActivation};
 pub use crate::Agent::Agent;
 pub use crate::AgentSet::AgentSet;
+pub use crate::AgentVars::{convert_var, var_from, AgentVars};
+pub use crate::Centrality::Centrality;
+pub use crate::Commands::CommandBuffer;
+pub use crate::Error::RuscapeError;
+pub use crate::Event::EventId;
+pub use crate::GetRng::RngState;
+pub use crate::Gillespie::{Gillespie, GillespieMethod};
+pub use crate::Link::LinkId;
+pub use crate::LinkRef::LinkRef;
+pub use crate::LinkSet::LinkSet;
 pub use crate::Model::Model;
+pub use crate::PatchRef::PatchRef;
 pub use crate::PatchSet::OriginLocation::*;
 pub use crate::PatchSet::PatchSet;
+pub use crate::Random::Random;
+pub use crate::Tie::TieMode;
+pub use crate::TurtleRef::TurtleRef;
 pub use crate::TurtleSet::TurtleSet;
-pub use crate::Variable::Variable;
+pub use crate::VarKey::VarKey;
+pub use crate::Variable::{VarType, Variable};
 pub use crate::World::{World, WorldRef};
 pub use fastrand;
+pub use ruscape_derive::{LinkVars, PatchVars, TurtleVars};
@@ -0,0 +1,83 @@
+use ruscape::prelude::*;
+use std::cell::RefCell;
+
+fn order(turtles: &TurtleSet) -> Vec<usize> {
+    let whos = RefCell::new(Vec::new());
+    turtles.ask(|t| whos.borrow_mut().push(t.who()));
+    whos.into_inner()
+}
+
+#[test]
+fn activation_is_selectable() {
+    let w = World::init_with_seed(20, 10, 10, Corner, true, 4);
+    let mut turtles = w.borrow().turtles();
+    let sequential = (0..20).collect::<Vec<_>>();
+    assert_ne!(order(&turtles), sequential);
+
+    w.borrow_mut().set_activation(Activation::Sequential);
+    assert_eq!(order(&turtles), sequential);
+
+    turtles.set_activation(Some(Activation::RandomWithReplacement));
+    let drawn = order(&turtles);
+    assert_eq!(drawn.len(), 20);
+    assert!(drawn.iter().all(|who| *who < 20));
+
+    let sorted = RefCell::new(Vec::new());
+    turtles.ask_sorted_by(
+        |t| std::cmp::Reverse(t.who()),
+        |t| sorted.borrow_mut().push(t.who()),
+    );
+    assert_eq!(sorted.into_inner(), (0..20).rev().collect::<Vec<_>>());
+}
+
+#[test]
+fn activation_can_sort_by_a_variable() {
+    let w = World::init_with_seed(5, 10, 10, Corner, true, 4);
+    let mut turtles = w.borrow().turtles();
+    turtles.own(&["wealth"]);
+    turtles.ask(|t| t.set("wealth", (t.who() as f64 - 2.).abs()));
+    turtles.set_activation(Some(Activation::SortedBy("wealth")));
+    assert_eq!(order(&turtles), vec![2, 1, 3, 0, 4]);
+
+    let log = RefCell::new(Vec::new());
+    turtles.ask_staged(&mut [&mut |t| log.borrow_mut().push(t.who())]);
+    assert_eq!(log.into_inner(), vec![2, 1, 3, 0, 4]);
+
+    w.borrow_mut()
+        .set_activation(Activation::SortedBy("wealth"));
+    let t4 = w.borrow().turtle(4);
+    t4.hatch(1);
+    let all = w.borrow().turtles();
+    assert_eq!(order(&all), vec![2, 1, 3, 0, 4, 5]);
+}
+
+#[test]
+fn staged_activation_runs_stages_in_turn() {
+    let w = World::init(5, 10, 10, Corner, true);
+    let turtles = w.borrow().turtles();
+    let log = RefCell::new(Vec::new());
+    turtles.ask_staged(&mut [&mut |_| log.borrow_mut().push('a'), &mut |_| {
+        log.borrow_mut().push('b')
+    }]);
+    assert_eq!(log.into_inner(), "aaaaabbbbb".chars().collect::<Vec<_>>());
+}
+
+#[test]
+fn links_follow_the_activation_order() {
+    let w = World::init_with_seed(5, 10, 10, Corner, true, 4);
+    let t0 = w.borrow().turtle(0);
+    for who in 1..5 {
+        let other = w.borrow().turtle(who);
+        t0.create_link_with(&other);
+    }
+    let mut links = w.borrow().links();
+    let visit = |links: &LinkSet| {
+        let whos = RefCell::new(Vec::new());
+        links.ask(|l| whos.borrow_mut().push(l.who()));
+        whos.into_inner()
+    };
+    let inserted = (1..5).map(|who| (0, who)).collect::<Vec<_>>();
+    assert_ne!(visit(&links), inserted);
+    links.set_activation(Some(Activation::Sequential));
+    assert_eq!(visit(&links), inserted);
+}
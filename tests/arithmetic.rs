@@ -0,0 +1,100 @@
+use ruscape::prelude::*;
+use std::convert::TryFrom;
+
+#[test]
+fn numeric_variables_promote() {
+    assert_eq!(Variable::Integer(2) + 3i64, Variable::Integer(5));
+    assert_eq!(Variable::Integer(2) + 0.5, Variable::Float(2.5));
+    assert_eq!(
+        Variable::Float(1.5) * Variable::Integer(2),
+        Variable::Float(3.)
+    );
+    assert_eq!(Variable::Integer(3) / 2i64, Variable::Float(1.5));
+    assert_eq!(10. - Variable::Integer(4), Variable::Float(6.));
+    assert_eq!(-Variable::Integer(4), Variable::Integer(-4));
+    let mut total = Variable::Integer(0);
+    total += 2i64;
+    total *= 1.5;
+    assert_eq!(total, 3.);
+    assert_eq!(Variable::Integer(1), Variable::Float(1.));
+    assert!(Variable::Integer(1) < Variable::Float(1.5));
+    assert!(Variable::Float(0.7) > 0.5);
+    assert!(Variable::String("a".to_string()) < "b");
+    assert_eq!(Variable::from("a").partial_cmp(&Variable::Integer(1)), None);
+    assert_eq!(
+        Variable::from("a").try_add(&Variable::Integer(1)),
+        Err(RuscapeError::InvalidOperation {
+            op: "+",
+            left: VarType::String,
+            right: VarType::Integer,
+        })
+    );
+    assert_eq!(
+        Variable::Integer(i64::MAX).try_add(&Variable::Integer(1)),
+        Err(RuscapeError::IntegerOverflow {
+            op: "+",
+            left: i64::MAX,
+            right: 1,
+        })
+    );
+    assert!(Variable::Integer(i64::MIN)
+        .try_sub(&Variable::Integer(1))
+        .is_err());
+    assert!(Variable::Integer(i64::MAX)
+        .try_mul(&Variable::Integer(2))
+        .is_err());
+    assert!(Variable::Integer(i64::MIN).try_neg().is_err());
+    assert_eq!(
+        Variable::from("a").try_neg(),
+        Err(RuscapeError::InvalidUnaryOperation {
+            op: "-",
+            operand: VarType::String,
+        })
+    );
+}
+
+#[test]
+fn variables_convert_to_primitives() {
+    assert_eq!(f64::try_from(Variable::Integer(2)), Ok(2.));
+    assert_eq!(i64::try_from(Variable::Integer(2)), Ok(2));
+    assert_eq!(bool::try_from(Variable::Boolean(true)), Ok(true));
+    assert_eq!(String::try_from(Variable::from("x")), Ok("x".to_string()));
+    assert_eq!(
+        i64::try_from(Variable::Float(2.)),
+        Err(RuscapeError::WrongType {
+            expected: VarType::Integer,
+            found: VarType::Float,
+        })
+    );
+}
+
+#[test]
+fn agents_have_typed_accessors() {
+    let w = World::init(1, 10, 10, Corner, true);
+    let mut patches = w.borrow().patches();
+    patches.own(vec!["payoff", "label"]);
+    let p = w.borrow().patch(0., 0.);
+    p.set("payoff", p.get("payoff") + 1.0);
+    p.set("label", "home");
+    assert_eq!(p.get_f64("payoff"), 1.);
+    assert_eq!(p.get_str("label"), "home");
+    assert_eq!(
+        p.try_get_as::<bool>("label"),
+        Err(RuscapeError::TypeMismatch {
+            agent: "(0, 0)".to_string(),
+            variable: "label".to_string(),
+            expected: VarType::Boolean,
+            found: VarType::String,
+        })
+    );
+}
+
+#[test]
+fn oversized_integers_do_not_wrap() {
+    assert_eq!(
+        Variable::try_from(u64::MAX as usize),
+        Err(RuscapeError::IntegerOutOfRange(u64::MAX as i128))
+    );
+    assert_eq!(Variable::try_from(7usize), Ok(Variable::Integer(7)));
+    assert_eq!(Variable::from(u32::MAX), Variable::Integer(u32::MAX as i64));
+}
@@ -0,0 +1,114 @@
+use ruscape::prelude::*;
+
+#[test]
+fn breeds_keep_their_own_agentsets() {
+    let w = World::init(2, 10, 10, Corner, true);
+    w.borrow_mut().declare_breed("sheep", &["wool"]);
+    w.borrow_mut().declare_breed("wolves", &["appetite"]);
+    let sheep = w.borrow_mut().create_breed("sheep", 5).unwrap();
+    w.borrow_mut().create_breed("wolves", 2).unwrap();
+    assert_eq!(w.borrow().turtles().count(), 9);
+    assert_eq!(w.borrow().breed("sheep").count(), 5);
+    assert_eq!(w.borrow().breed("wolves").count(), 2);
+    assert!(sheep.all(|t| t.breed() == Some("sheep".to_string())));
+    assert_eq!(w.borrow().turtle(0).breed(), None);
+
+    let s = sheep.one_of().unwrap();
+    assert_eq!(s.get("wool"), Variable::default());
+    s.set("wool", 3.);
+    assert!(s.try_get("appetite").is_err());
+
+    s.set_breed("wolves").unwrap();
+    assert_eq!(w.borrow().breed("sheep").count(), 4);
+    assert_eq!(w.borrow().breed("wolves").count(), 3);
+    assert!(s.try_get("wool").is_err());
+    assert_eq!(s.get("appetite"), Variable::default());
+
+    s.set_breed("turtles").unwrap();
+    assert_eq!(s.breed(), None);
+    assert_eq!(w.borrow().breed("wolves").count(), 2);
+    assert_eq!(
+        s.set_breed("cows"),
+        Err(RuscapeError::UnknownBreed("cows".to_string()))
+    );
+
+    let wolf = w.borrow().breed("wolves").one_of().unwrap();
+    wolf.die();
+    assert_eq!(w.borrow().breed("wolves").count(), 1);
+
+    assert_eq!(
+        w.borrow_mut().create_breed("cows", 2).err(),
+        Some(RuscapeError::UnknownBreed("cows".to_string()))
+    );
+    assert_eq!(w.borrow().turtles().count(), 8);
+}
+
+#[test]
+fn declaring_a_breed_again_keeps_its_members() {
+    let w = World::init(0, 10, 10, Corner, true);
+    w.borrow_mut().declare_breed("sheep", &["wool", "age"]);
+    let flock = w.borrow_mut().create_breed("sheep", 3).unwrap();
+    flock.ask(|t| t.set("age", 2));
+    w.borrow_mut().declare_breed("sheep", &["age", "grazing"]);
+    assert_eq!(w.borrow().breed("sheep").count(), 3);
+    assert!(flock.all(|t| t.get("age") == 2 && t.get("grazing") == Variable::default()));
+    assert!(flock.all(|t| t.try_get("wool").is_err()));
+    let lamb = w.borrow_mut().create_breed("sheep", 1).unwrap();
+    assert!(lamb.all(|t| t.try_get("grazing").is_ok() && t.try_get("wool").is_err()));
+}
+
+#[test]
+fn hatch_and_here_respect_breeds() {
+    let w = World::init(0, 10, 10, Corner, true);
+    w.borrow_mut().declare_breed("sheep", &["wool"]);
+    w.borrow_mut().declare_breed("wolves", Vec::<&str>::new());
+    let flock = w.borrow_mut().create_breed("sheep", 1).unwrap();
+    let sheep = flock.one_of().unwrap();
+    sheep.set("wool", 2.);
+    let lambs = sheep.hatch(3);
+    assert!(lambs.all(|t| t.breed() == Some("sheep".to_string())));
+    assert!(lambs.all(|t| t.get("wool") == 2.));
+    assert_eq!(w.borrow().breed("sheep").count(), 4);
+
+    let cubs = sheep.hatch_breed("wolves", 2).unwrap();
+    assert!(cubs.all(|t| t.try_get("wool").is_err()));
+    assert_eq!(w.borrow().breed("wolves").count(), 2);
+    assert_eq!(sheep.turtles_here().count(), 6);
+    assert_eq!(sheep.breed_here("sheep").count(), 4);
+    assert_eq!(sheep.patch_here().breed_on("wolves").count(), 2);
+}
+
+#[test]
+fn link_breeds_layer_over_the_link_graphs() {
+    let w = World::init(3, 10, 10, Corner, true);
+    w.borrow_mut().declare_link_breed("friendships", false);
+    w.borrow_mut().declare_link_breed("follows", true);
+    let t0 = w.borrow().turtle(0);
+    let t1 = w.borrow().turtle(1);
+    let t2 = w.borrow().turtle(2);
+    t0.create_breed_link_with("friendships", &t1).unwrap();
+    t0.create_breed_link_to("follows", &t2).unwrap();
+    t1.create_link_with(&t2);
+    assert_eq!(
+        t0.create_breed_link_with("follows", &t1),
+        Err(RuscapeError::LinkBreedDirection("follows".to_string()))
+    );
+
+    assert_eq!(w.borrow().link_breed("friendships").count(), 1);
+    assert_eq!(w.borrow().link_breed("follows").count(), 1);
+    assert_eq!(t0.my_breed_links("friendships").count(), 1);
+    assert_eq!(t1.my_breed_links("friendships").count(), 1);
+    assert_eq!(t1.my_links().count(), 2);
+    assert_eq!(t2.my_breed_links("follows").count(), 1);
+    assert_eq!(t0.breed_link_neighbors("friendships").count(), 1);
+    assert!(t0.out_breed_link_neighbors("follows").contains_key(&2));
+    assert!(t0.in_breed_link_neighbors("follows").is_empty());
+    assert!(t2.in_breed_link_neighbors("follows").contains_key(&0));
+    assert!(t2.out_breed_link_neighbors("follows").is_empty());
+    assert_eq!(
+        t0.try_breed_link_neighbors("follows").err(),
+        Some(RuscapeError::LinkBreedDirection("follows".to_string()))
+    );
+    assert!(t0.try_out_breed_link_neighbors("friendships").is_err());
+    assert!(t0.out_link_neighbors().contains_key(&2));
+}
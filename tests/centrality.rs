@@ -0,0 +1,120 @@
+use ruscape::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-6
+}
+
+fn values(w: &Rc<RefCell<World>>, kind: Centrality, weight: Option<&str>) -> Vec<f64> {
+    let values = w.borrow().centrality(kind, weight).unwrap();
+    values.into_iter().map(|(_, v)| v).collect()
+}
+
+#[test]
+fn centralities_of_a_star() {
+    let world = World::init(0, 10, 10, Center, true);
+    world.borrow_mut().star_network(5, None).unwrap();
+
+    let degree = values(&world, Centrality::Degree, None);
+    assert!(close(degree[0], 1.) && close(degree[1], 0.25));
+    let betweenness = values(&world, Centrality::Betweenness, None);
+    assert!(close(betweenness[0], 1.) && close(betweenness[1], 0.));
+    let closeness = values(&world, Centrality::Closeness, None);
+    assert!(close(closeness[0], 1.) && close(closeness[1], 4. / 7.));
+    let eigenvector = values(&world, Centrality::Eigenvector, None);
+    assert!(close(eigenvector[0] / eigenvector[1], 2.));
+    let page_rank = values(&world, Centrality::PageRank { damping: 0.85 }, None);
+    assert!(close(page_rank.iter().sum::<f64>(), 1.));
+    assert!(page_rank[0] > page_rank[1]);
+    assert!(close(page_rank[1], page_rank[4]));
+}
+
+#[test]
+fn directed_and_weighted_links() {
+    let world = World::init(3, 10, 10, Center, true);
+    let t0 = world.borrow().turtle(0);
+    let t1 = world.borrow().turtle(1);
+    let t2 = world.borrow().turtle(2);
+    t0.create_link_to(&t1);
+    t1.create_link_to(&t2);
+    let betweenness = values(&world, Centrality::Betweenness, None);
+    assert!(close(betweenness[1], 0.5) && close(betweenness[0], 0.));
+    t2.create_link_to(&t0);
+    let page_rank = values(&world, Centrality::PageRank { damping: 0.85 }, None);
+    assert!(page_rank.iter().all(|&p| close(p, 1. / 3.)));
+
+    world.borrow_mut().clear_links();
+    t0.create_link_with(&t1);
+    t1.create_link_with(&t2);
+    let mut links = world.borrow().links();
+    links.own(&["length"]);
+    t0.link_with(&t1).unwrap().set("length", 1.);
+    t1.link_with(&t2).unwrap().set("length", 3.);
+    let closeness = values(&world, Centrality::Closeness, Some("length"));
+    assert!(close(closeness[0], 2. / 5.));
+    assert!(close(closeness[1], 2. / 4.));
+    assert_eq!(
+        world
+            .borrow()
+            .centrality(Centrality::Degree, Some("strength")),
+        Err(RuscapeError::UnknownVariable {
+            agent: "(0, 1)".to_string(),
+            variable: "strength".to_string(),
+        })
+    );
+}
+
+#[test]
+fn bad_weights_and_damping_are_errors() {
+    let world = World::init(3, 10, 10, Center, true);
+    let t0 = world.borrow().turtle(0);
+    let t1 = world.borrow().turtle(1);
+    let t2 = world.borrow().turtle(2);
+    t0.create_link_with(&t1);
+    t1.create_link_with(&t2);
+    let mut links = world.borrow().links();
+    links.own(&["length"]);
+    t0.link_with(&t1).unwrap().set("length", 1.);
+    t1.link_with(&t2).unwrap().set("length", 0.);
+    let centrality = |kind| world.borrow().centrality(kind, Some("length"));
+    assert!(centrality(Centrality::Degree).is_ok());
+    assert!(matches!(
+        centrality(Centrality::Betweenness),
+        Err(RuscapeError::InvalidNetwork(_))
+    ));
+    assert!(matches!(
+        centrality(Centrality::Closeness),
+        Err(RuscapeError::InvalidNetwork(_))
+    ));
+    t1.link_with(&t2).unwrap().set("length", f64::NAN);
+    assert!(centrality(Centrality::Degree).is_err());
+    t1.link_with(&t2).unwrap().set("length", f64::INFINITY);
+    assert!(centrality(Centrality::Eigenvector).is_err());
+    t1.link_with(&t2).unwrap().set("length", 2.);
+    assert!(centrality(Centrality::Betweenness).is_ok());
+    assert!(matches!(
+        centrality(Centrality::PageRank { damping: 1.5 }),
+        Err(RuscapeError::InvalidNetwork(_))
+    ));
+    assert!(centrality(Centrality::PageRank { damping: f64::NAN }).is_err());
+    assert!(centrality(Centrality::PageRank { damping: 1. }).is_ok());
+}
+
+#[test]
+fn centrality_is_written_to_turtle_variables() {
+    let world = World::init(0, 10, 10, Center, true);
+    let mut ring = world.borrow_mut().ring_lattice(6, 2, None).unwrap();
+    assert!(world
+        .borrow()
+        .set_centrality("betweenness", Centrality::Betweenness, None)
+        .is_err());
+    ring.own(&["betweenness"]);
+    world
+        .borrow()
+        .set_centrality("betweenness", Centrality::Betweenness, None)
+        .unwrap();
+    let first = ring.values().next().unwrap().get_f64("betweenness");
+    assert!(first > 0.);
+    assert!(ring.all(|t| close(t.get_f64("betweenness"), first)));
+}
@@ -0,0 +1,103 @@
+use ruscape::prelude::*;
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[test]
+fn moves_apply_after_ask() {
+    let w = World::init(10, 10, 10, Corner, true);
+    let turtles = w.borrow().turtles();
+    turtles.ask_deferred(|t, commands| {
+        commands.setxy(t, 3., 4.);
+        assert_eq!((t.xcor(), t.ycor()), (0., 0.));
+    });
+    assert_eq!(w.borrow().patch(3., 4.).turtles_on().count(), 10);
+}
+
+#[test]
+fn deaths_and_births_apply_after_ask() {
+    let w = World::init_with_seed(6, 10, 10, Corner, true, 2);
+    let turtles = w.borrow().turtles();
+    turtles.ask_deferred(|t, commands| {
+        if t.who() % 2 == 0 {
+            commands.die(t);
+            commands.hatch(t, 1, |_| panic!("dead turtles do not hatch"));
+        } else {
+            commands.hatch(t, 2, |hatched| {
+                hatched.ask(|h| {
+                    h.setxy(5., 5.);
+                });
+            });
+        }
+        assert_eq!(w.borrow().turtles().count(), 6);
+    });
+    assert!(turtles.all(|t| t.who() % 2 == 1));
+    assert_eq!(w.borrow().turtles().count(), 3 + 3 * 2);
+    assert_eq!(w.borrow().patch(5., 5.).turtles_on().count(), 6);
+
+    let patches = w.borrow().patches();
+    patches.ask_deferred(|p, commands| {
+        if (p.pxcor(), p.pycor()) == (2, 3) {
+            commands.sprout(p, 4, |sprouted| assert_eq!(sprouted.count(), 4));
+        }
+    });
+    assert_eq!(w.borrow().patch(2., 3.).turtles_on().count(), 4);
+    assert_eq!(w.borrow().turtles().count(), 9 + 4);
+}
+
+#[test]
+fn queued_links_and_runs_wait_for_apply() {
+    let w = World::init_with_seed(3, 10, 10, Corner, true, 2);
+    let (t0, t1, t2) = {
+        let w = w.borrow();
+        (w.turtle(0), w.turtle(1), w.turtle(2))
+    };
+    let ran = Rc::new(Cell::new(0));
+    let mut commands = CommandBuffer::new();
+    commands.create_link_with(&t0, &t1);
+    commands.create_link_to(&t0, &t2);
+    let counter = ran.clone();
+    commands.run(move || counter.set(counter.get() + 1));
+    assert_eq!(commands.len(), 3);
+    assert_eq!(w.borrow().links().count(), 0);
+    assert_eq!(ran.get(), 0);
+    commands.apply();
+    assert!(commands.is_empty());
+    assert_eq!(ran.get(), 1);
+    assert!(t0.link_with(&t1).is_some());
+    assert!(t0.out_link_to(&t2).is_some());
+    assert_eq!(w.borrow().links().count(), 2);
+}
+
+#[test]
+fn links_to_dying_or_linked_turtles_are_skipped() {
+    let w = World::init_with_seed(4, 10, 10, Corner, true, 2);
+    let (t0, t1, t2) = {
+        let w = w.borrow();
+        (w.turtle(0), w.turtle(1), w.turtle(2))
+    };
+    let mut commands = CommandBuffer::new();
+    commands.die(&t1);
+    commands.create_link_with(&t0, &t1);
+    commands.create_link_to(&t0, &t2);
+    commands.die(&t2);
+    commands.create_link_to(&t0, &t1);
+    commands.apply();
+    assert!(!t1.is_alive() && !t2.is_alive());
+    assert_eq!(t0.my_links().count(), 0);
+    assert_eq!(w.borrow().links().count(), 0);
+
+    let t3 = w.borrow().turtle(3);
+    t0.create_link_to(&t3);
+    commands.create_link_with(&t3, &t0);
+    commands.create_link_to(&t3, &t0);
+    commands.apply();
+    assert_eq!(t0.my_links().count(), 0);
+    assert!(t3.out_link_to(&t0).is_some());
+    assert_eq!(w.borrow().links().count(), 2);
+
+    commands.create_link_with(&t3, &t3);
+    commands.setxy(&t3, 1., 1.);
+    assert_eq!(commands.try_apply(), Err(RuscapeError::SelfLink(3)));
+    assert!(commands.is_empty());
+    assert_eq!(w.borrow().links().count(), 2);
+}
@@ -0,0 +1,135 @@
+use ruscape::prelude::*;
+
+#[test]
+fn agentsets_skip_dead_members() {
+    let w = World::init(10, 10, 10, Corner, true);
+    let turtles = w.borrow().turtles();
+    let t3 = w.borrow().turtle(3);
+    t3.die();
+    assert!(!t3.is_alive());
+    assert_eq!(turtles.len(), 10);
+    assert_eq!(turtles.count(), 9);
+    assert_eq!(turtles.live().len(), 9);
+    assert!(turtles
+        .live()
+        .into_iter()
+        .all(|idx| turtles[idx].is_alive()));
+    assert!(turtles.all(|t| t.is_alive()));
+    assert!(!turtles.any(|t| t.who() == 3));
+    assert_eq!(turtles.with(|_| true).count(), 9);
+    assert_eq!(turtles.n_of(9).count(), 9);
+    assert_eq!(turtles.max_one_of(|t| t.who()).unwrap().who(), 9);
+
+    let t5 = w.borrow().turtle(5);
+    let mut asked = 0;
+    turtles.ask(|t| {
+        if t.who() == 0 {
+            t5.die();
+        }
+        asked += 1;
+    });
+    assert!(asked == 8 || asked == 9);
+    assert_eq!(turtles.count(), 8);
+    (0..50).for_each(|_| assert!(turtles.one_of().unwrap().is_alive()));
+    (0..50).for_each(|_| {
+        let t = turtles.one_of_weighted_by(|t| t.who() + 1).unwrap();
+        assert!(t.is_alive());
+    });
+    let drawn = turtles.n_of_weighted_by(50, |_| 1);
+    assert!(drawn.values().all(|t| t.is_alive()));
+    let mut asked = 0;
+    turtles.ask_with(Activation::RandomWithReplacement, |_| asked += 1);
+    assert_eq!(asked, 8);
+}
+
+#[test]
+fn dead_agents_report_a_clear_error() {
+    let w = World::init(3, 10, 10, Corner, true);
+    let t0 = w.borrow().turtle(0);
+    let t1 = w.borrow().turtle(1);
+    w.borrow_mut().reset_ticks();
+    t0.schedule(1., 0, |t| {
+        t.fd(1.);
+    })
+    .unwrap();
+    t0.die();
+    t0.die();
+    assert_eq!(
+        t0.check_alive(),
+        Err(RuscapeError::DeadAgent("0".to_string()))
+    );
+    assert_eq!(
+        t0.schedule(2., 0, |_| {}),
+        Err(RuscapeError::DeadAgent("0".to_string()))
+    );
+    assert!(t1.check_alive().is_ok());
+    let dead = Some(RuscapeError::DeadAgent("0".to_string()));
+    assert_eq!(t0.try_setxy(1., 1.).err(), dead.clone());
+    assert_eq!(t0.try_fd(1.).err(), dead.clone());
+    assert_eq!(t0.try_hatch(2).err(), dead.clone());
+    assert_eq!(t0.try_link_neighbors().err(), dead.clone());
+    assert_eq!(t0.try_my_links().err(), dead);
+    assert!(t1.try_fd(1.).is_ok());
+    World::run_until(&w, 3.).unwrap();
+    assert!(w
+        .borrow()
+        .patch(0., 0.)
+        .turtles_on()
+        .values()
+        .all(|t| t.is_alive()));
+}
+
+#[test]
+#[should_panic(expected = "agent 0 is dead")]
+fn moving_a_dead_turtle_panics() {
+    let w = World::init(2, 10, 10, Corner, true);
+    let t0 = w.borrow().turtle(0);
+    t0.die();
+    t0.fd(1.);
+}
+
+#[test]
+#[should_panic(expected = "agent 1 is dead")]
+fn linking_to_a_dead_turtle_panics() {
+    let w = World::init(2, 10, 10, Corner, true);
+    let t0 = w.borrow().turtle(0);
+    let t1 = w.borrow().turtle(1);
+    t1.die();
+    t0.create_link_with(&t1);
+}
+
+#[test]
+fn looking_up_links_of_a_dead_turtle_finds_nothing() {
+    let w = World::init(3, 10, 10, Corner, true);
+    let t0 = w.borrow().turtle(0);
+    let t1 = w.borrow().turtle(1);
+    let t2 = w.borrow().turtle(2);
+    t0.create_link_with(&t1);
+    t2.create_link_to(&t0);
+    t0.die();
+    assert!(t0.link_with(&t1).is_none());
+    assert!(t0.in_link_from(&t2).is_none());
+    assert!(t0.out_link_to(&t2).is_none());
+    assert!(t2.out_link_to(&t0).is_none());
+}
+
+#[test]
+fn drawing_from_dead_or_weightless_sets_is_an_error() {
+    let w = World::init_with_seed(3, 10, 10, Corner, true, 5);
+    let turtles = w.borrow().turtles();
+    assert_eq!(
+        turtles.try_n_of_weighted_by(2, |_| 0).err(),
+        Some(RuscapeError::EmptyAgentSet)
+    );
+    assert!(turtles.n_of_weighted_by(2, |_| 0).is_empty());
+    assert!(!turtles.n_of_with_repeats(5).is_empty());
+    assert_eq!(turtles.try_n_of(4).err(), Some(RuscapeError::EmptyAgentSet));
+    turtles.ask(|t| t.die());
+    assert_eq!(turtles.count(), 0);
+    assert_eq!(
+        turtles.try_one_of().err(),
+        Some(RuscapeError::EmptyAgentSet)
+    );
+    assert!(turtles.n_of_weighted_by(1, |_| 1).is_empty());
+    assert!(turtles.try_n_of_weighted_by(1, |_| 1).is_err());
+}
@@ -0,0 +1,38 @@
+use ruscape::prelude::*;
+
+#[test]
+fn empty_turtlesets_are_values() {
+    let w = World::init(5, 10, 10, Corner, true);
+    let turtles = w.borrow().turtles();
+    let none = turtles.with(|t| t.who() > 10);
+    assert_eq!(none.count(), 0);
+    assert!(!none.any(|_| true));
+    assert!(none.all(|_| false));
+    assert!(none.one_of().is_none());
+    assert!(none.max_one_of(|t| t.who()).is_none());
+    assert!(none.min_one_of(|t| t.who()).is_none());
+    assert_eq!(none.clone().count(), 0);
+    assert_eq!(turtles.n_of(0).count(), 0);
+    assert_eq!(turtles.n_of_with_repeats(0).count(), 0);
+    assert!(turtles.one_of_weighted_by(|_| 0).is_none());
+    none.ask(|_| panic!("asked an empty agentset"));
+    assert!(none.report(|t| t.who()).is_empty());
+    assert_eq!(w.borrow_mut().crt(0).count(), 0);
+
+    let mut some = none.clone();
+    some += turtles.n_of(2);
+    assert_eq!(some.count(), 2);
+    assert!(some.one_of().is_some());
+}
+
+#[test]
+fn isolated_turtles_have_empty_links() {
+    let w = World::init(2, 10, 10, Corner, true);
+    let t0 = w.borrow().turtle(0);
+    let links = t0.my_links();
+    assert_eq!(links.count(), 0);
+    assert!(links.one_of().is_none());
+    assert_eq!(links.clone().count(), 0);
+    assert_eq!(t0.link_neighbors().count(), 0);
+    assert!(w.borrow().patches().with(|_| false).one_of().is_none());
+}
@@ -0,0 +1,54 @@
+use ruscape::prelude::*;
+
+#[test]
+fn missing_variables_are_reported_with_the_agent() {
+    let w = World::init(2, 10, 10, Corner, true);
+    let mut turtles = w.borrow().turtles();
+    turtles.own(vec!["energy"]);
+    let t1 = w.borrow().turtle(1);
+    assert!(t1.try_set("energy", 3.).is_ok());
+    assert_eq!(t1.try_get("energy"), Ok(Variable::Float(3.)));
+    assert_eq!(
+        t1.try_get("wealth"),
+        Err(RuscapeError::UnknownVariable {
+            agent: "1".to_string(),
+            variable: "wealth".to_string()
+        })
+    );
+    assert!(t1.try_set("wealth", 1.).is_err());
+    let p = w.borrow().patch(2., 3.);
+    assert_eq!(
+        p.try_get("grass").unwrap_err().to_string(),
+        "agent (2, 3) does not own a variable named grass"
+    );
+}
+
+#[test]
+fn picking_from_an_emptied_agentset_is_an_error() {
+    let w = World::init(2, 10, 10, Corner, true);
+    let turtles = w.borrow().turtles();
+    assert!(turtles.try_one_of().is_ok());
+    let (t0, t1) = (w.borrow().turtle(0), w.borrow().turtle(1));
+    t0.die();
+    t1.die();
+    assert_eq!(turtles.try_one_of(), Err(RuscapeError::EmptyAgentSet));
+    assert_eq!(w.borrow().try_turtle(0), Err(RuscapeError::NoSuchTurtle(0)));
+}
+
+#[test]
+fn dropped_worlds_are_an_error() {
+    let w = World::init(1, 10, 10, Corner, true);
+    let t0 = w.borrow().turtle(0);
+    let turtles = w.borrow().turtles();
+    assert!(t0.try_world().is_ok());
+    drop(w);
+    assert_eq!(t0.try_world().unwrap_err(), RuscapeError::NoWorld);
+    assert_eq!(turtles.try_world().unwrap_err(), RuscapeError::NoWorld);
+}
+
+#[test]
+fn variables_of_different_types_are_not_equal() {
+    assert!(Variable::Boolean(true) != 1i64);
+    assert!(Variable::Integer(1) == 1i64);
+    assert!(Variable::Boolean(true) != "true");
+}
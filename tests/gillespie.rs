@@ -0,0 +1,78 @@
+use ruscape::prelude::*;
+use std::cell::Cell;
+use std::rc::Rc;
+
+fn sir(method: GillespieMethod) -> (usize, usize, f64) {
+    let w = World::init_with_seed(200, 10, 10, Corner, true, 11);
+    w.borrow_mut().reset_ticks();
+    let mut turtles = w.borrow().turtles();
+    turtles.own(vec!["state"]);
+    turtles.ask(|t| t.set("state", "S"));
+    turtles.n_of(5).ask(|t| t.set("state", "I"));
+
+    let infected = {
+        let turtles = turtles.clone();
+        move || turtles.with(|t| t.get("state") == "I").count() as f64
+    };
+    let mut gillespie = Gillespie::new(method);
+    gillespie.add_agents(
+        |w| w.turtles(),
+        move |t| {
+            if t.get("state") == "S" {
+                0.002 * infected()
+            } else {
+                0.
+            }
+        },
+        |t| t.set("state", "I"),
+    );
+    gillespie.add_agents(
+        |w| w.turtles(),
+        |t| if t.get("state") == "I" { 0.1 } else { 0. },
+        |t| t.set("state", "R"),
+    );
+    gillespie.run_until(&w, 50.).unwrap();
+    let count = |s: &'static str| turtles.with(|t| t.get("state") == s).count();
+    let ticks = w.borrow().ticks().unwrap();
+    (count("I"), count("R"), ticks)
+}
+
+#[test]
+fn continuous_time_sir() {
+    for &method in [GillespieMethod::Direct, GillespieMethod::NextReaction].iter() {
+        let (infected, recovered, ticks) = sir(method);
+        assert_eq!(sir(method), (infected, recovered, ticks));
+        assert!(recovered > 5);
+        assert_eq!(ticks, 50.);
+    }
+}
+
+#[test]
+fn agents_born_or_dying_during_the_run_join_or_leave() {
+    let w = World::init_with_seed(2, 10, 10, Corner, true, 3);
+    w.borrow_mut().reset_ticks();
+    let born_fired = Rc::new(Cell::new(false));
+    let mut gillespie = Gillespie::new(GillespieMethod::NextReaction);
+    gillespie
+        .add_agents(|w| w.turtles(), |_| 1., {
+            let born_fired = born_fired.clone();
+            move |t: &TurtleRef| {
+                assert!(t.is_alive());
+                if t.who() >= 2 {
+                    born_fired.set(true);
+                }
+                t.hatch(1);
+            }
+        })
+        .add_agents(
+            |w| w.turtles(),
+            |_| 0.5,
+            |t| {
+                assert!(t.is_alive());
+                t.die();
+            },
+        );
+    gillespie.run_until(&w, 4.).unwrap();
+    assert!(born_fired.get());
+    assert!(w.borrow().turtles().len() > 2);
+}
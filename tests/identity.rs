@@ -0,0 +1,41 @@
+use ruscape::prelude::*;
+
+#[test]
+fn who_numbers_are_never_reused() {
+    let w = World::init(5, 10, 10, Corner, true);
+    let t4 = w.borrow().turtle(4);
+    let t0 = w.borrow().turtle(0);
+    t4.create_link_with(&t0);
+    let t1 = w.borrow().turtle(1);
+    t1.die();
+    assert_eq!(t4.who(), 4);
+    assert_eq!(w.borrow().turtle(4), t4);
+    assert_eq!(
+        t4.link_neighbors().keys().cloned().collect::<Vec<_>>(),
+        vec![0]
+    );
+
+    let born = w.borrow_mut().crt(2);
+    let mut whos = born.keys().cloned().collect::<Vec<_>>();
+    whos.sort();
+    assert_eq!(whos, vec![5, 6]);
+    assert_eq!(t4.hatch(1).keys().cloned().collect::<Vec<_>>(), vec![7]);
+    assert_eq!(w.borrow().turtles().count(), 7);
+    assert_eq!(w.borrow().patch(0., 0.).turtles_on().count(), 7);
+}
+
+#[test]
+fn deferred_deaths_remove_the_right_turtles() {
+    let w = World::init(100, 10, 10, Corner, true);
+    let turtles = w.borrow().turtles();
+    turtles.ask_deferred(|t, commands| {
+        if t.who() % 2 == 0 {
+            commands.die(t);
+            commands.die(t);
+        }
+        assert_eq!(w.borrow().turtles().count(), 100);
+    });
+    assert_eq!(w.borrow().turtles().count(), 50);
+    assert!(w.borrow().turtles().all(|t| t.who() % 2 == 1));
+    assert!(w.borrow().turtles().iter().all(|(&who, t)| who == t.who()));
+}
@@ -0,0 +1,33 @@
+use ruscape::prelude::*;
+
+#[test]
+fn variables_can_be_named_at_runtime() {
+    let header = String::from("wealth,age,strategy");
+    let names = header.split(',').map(String::from).collect::<Vec<_>>();
+    let w = World::init(2, 10, 10, Corner, true);
+    let mut turtles = w.borrow().turtles();
+    turtles.own(&names);
+    turtles
+        .own_typed(&format!("{}_max", names[0]), VarType::Float, 100.)
+        .unwrap();
+
+    let t0 = w.borrow().turtle(0);
+    for (value, name) in names.iter().enumerate() {
+        t0.set(name, value as i64);
+    }
+    let key = String::from("age");
+    assert_eq!(t0.get(&key), Variable::Integer(1));
+    assert_eq!(t0.get_f64("wealth_max"), 100.);
+    assert!(t0.try_set("wealth_max", 1i64).is_err());
+    assert!(t0.try_get("height").is_err());
+}
+
+#[test]
+fn var_keys_compare_by_name() {
+    let a = VarKey::from(String::from("energy"));
+    let b = VarKey::from("energy");
+    assert_eq!(a, b);
+    assert!(std::ptr::eq(a.as_str(), a.clone().as_str()));
+    assert_eq!(a.as_str(), "energy");
+    assert_eq!(format!("{}", a), "energy");
+}
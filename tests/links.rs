@@ -0,0 +1,181 @@
+use ruscape::prelude::*;
+
+#[test]
+fn links_know_their_ends() {
+    let w = World::init(3, 10, 10, Corner, false);
+    let t0 = w.borrow().turtle(0);
+    let t1 = w.borrow().turtle(1);
+    let t2 = w.borrow().turtle(2);
+    t0.setxy(0., 0.);
+    t1.setxy(3., 4.);
+    t2.setxy(0., 2.);
+    t1.create_link_with(&t0);
+    t2.create_link_to(&t0);
+
+    let links = t0.my_links();
+    assert_eq!(links.len(), 1);
+    let l = links.values().next().unwrap().clone();
+    assert_eq!(l.who(), (0, 1));
+    assert_eq!(l.end1(), t0);
+    assert_eq!(l.end2(), t1);
+    assert_eq!(l.other_end(&t1), t0);
+    assert_eq!(l.both_ends().len(), 2);
+    assert!((l.link_length() - 5.).abs() < 1e-9);
+    assert!((l.link_heading() - t0.towards(&t1)).abs() < 1e-9);
+    assert_eq!(l.borrow().to_ref(), l);
+
+    let d = t0.my_in_links().values().next().unwrap().clone();
+    assert!(d.is_directed());
+    assert_eq!(d.who(), (2, 0));
+    assert_eq!(d.end1(), t2);
+    assert!((d.link_heading() - 180.).abs() < 1e-9);
+}
+
+#[test]
+fn turtles_cannot_link_to_themselves() {
+    let w = World::init(1, 10, 10, Corner, false);
+    let t0 = w.borrow().turtle(0);
+    assert_eq!(t0.try_create_link_with(&t0), Err(RuscapeError::SelfLink(0)));
+    assert_eq!(t0.try_create_link_to(&t0), Err(RuscapeError::SelfLink(0)));
+    assert_eq!(t0.try_create_link_from(&t0), Err(RuscapeError::SelfLink(0)));
+    assert_eq!(w.borrow().links().count(), 0);
+    assert_eq!(t0.link_neighbors().count(), 0);
+}
+
+#[test]
+fn links_own_variables() {
+    let w = World::init(3, 10, 10, Corner, true);
+    let t0 = w.borrow().turtle(0);
+    let mut others = w.borrow().turtles();
+    others.delete(&t0);
+    t0.create_links_with(&mut others);
+    let mut links = t0.my_links();
+    links.own(&["weight"]);
+    links.ask(|l| l.set("weight", l.who().1 as f64));
+    assert_eq!(t0.my_links().report(|l| l.get_f64("weight")).len(), 2);
+    assert!(t0
+        .my_links()
+        .all(|l| l.get("weight") == l.end2().who() as i64));
+}
+
+#[test]
+fn links_die_with_their_ends() {
+    let w = World::init(3, 10, 10, Corner, true);
+    let t0 = w.borrow().turtle(0);
+    let t1 = w.borrow().turtle(1);
+    let t2 = w.borrow().turtle(2);
+    t0.create_link_with(&t1);
+    t0.create_link_to(&t2);
+    let l01 = t0.my_links().values().next().unwrap().clone();
+    let l02 = t0.my_out_links().values().next().unwrap().clone();
+
+    l01.die();
+    l01.die();
+    assert!(!l01.is_alive());
+    assert_eq!(t0.my_links().count(), 0);
+    assert_eq!(t1.link_neighbors().count(), 0);
+    assert!(l01.check_alive().is_err());
+
+    t2.die();
+    assert!(!l02.is_alive());
+    assert_eq!(t0.my_out_links().count(), 0);
+
+    t0.create_link_with(&t1);
+    let l = t0.my_links().values().next().unwrap().clone();
+    w.borrow_mut().clear_links();
+    assert!(!l.is_alive());
+}
+
+#[test]
+fn link_rngs_are_part_of_the_rng_state() {
+    let w = World::init_with_seed(2, 10, 10, Corner, true, 42);
+    let t0 = w.borrow().turtle(0);
+    let t1 = w.borrow().turtle(1);
+    t0.create_link_with(&t1);
+    let l = t0.my_links().values().next().unwrap().clone();
+    let state = w.borrow().rng_state();
+    let first = l.rng().u64(..);
+    w.borrow_mut().set_rng_state(&state);
+    assert_eq!(l.rng().u64(..), first);
+    w.borrow_mut().random_seed(42);
+    assert_eq!(l.rng().u64(..), first);
+}
+
+#[test]
+fn link_lookup_between_turtles() {
+    let w = World::init(4, 10, 10, Corner, true);
+    let t0 = w.borrow().turtle(0);
+    let t1 = w.borrow().turtle(1);
+    let t2 = w.borrow().turtle(2);
+    let t3 = w.borrow().turtle(3);
+    t1.create_link_with(&t0);
+    t0.create_link_to(&t2);
+
+    assert!(t0.is_link_neighbor(&t1));
+    assert!(t1.is_link_neighbor(&t0));
+    assert!(!t0.is_link_neighbor(&t2));
+    assert!(t0.is_out_link_neighbor(&t2));
+    assert!(t2.is_in_link_neighbor(&t0));
+    assert!(!t2.is_out_link_neighbor(&t0));
+
+    assert_eq!(t1.link_with(&t0).unwrap().who(), (0, 1));
+    assert_eq!(t0.link_with(&t1), t1.link_with(&t0));
+    assert_eq!(t0.out_link_to(&t2), t2.in_link_from(&t0));
+    assert_eq!(t0.out_link_to(&t2).unwrap().who(), (0, 2));
+    assert!(t2.out_link_to(&t0).is_none());
+    assert!(t0.link_with(&t3).is_none());
+
+    assert_eq!(w.borrow().links().count(), 2);
+    t0.link_with(&t1).unwrap().die();
+    assert!(!t0.is_link_neighbor(&t1));
+    assert_eq!(w.borrow().links().count(), 1);
+    t3.die();
+    assert!(!t0.is_link_neighbor(&t3));
+    assert!(t0.link_with(&t3).is_none());
+}
+
+#[test]
+fn duplicate_links_are_not_created() {
+    let w = World::init(3, 10, 10, Corner, true);
+    w.borrow_mut().declare_link_breed("friendships", false);
+    let t0 = w.borrow().turtle(0);
+    let t1 = w.borrow().turtle(1);
+    let t2 = w.borrow().turtle(2);
+    t0.create_link_with(&t1);
+    t1.create_link_with(&t0);
+    t0.create_link_with(&t1);
+    assert_eq!(t0.my_links().count(), 1);
+
+    t0.create_link_to(&t2);
+    t0.create_link_to(&t2);
+    t2.create_link_from(&t0);
+    assert_eq!(t0.my_out_links().count(), 1);
+    t2.create_link_to(&t0);
+    assert_eq!(t0.my_in_links().count(), 1);
+    assert_eq!(
+        t2.try_create_link_with(&t0),
+        Err(RuscapeError::LinkExists(0, 2))
+    );
+    assert_eq!(t0.my_links().count(), 1);
+
+    t1.create_breed_link_with("friendships", &t2).unwrap();
+    t2.create_breed_link_with("friendships", &t1).unwrap();
+    assert_eq!(t1.my_breed_links("friendships").count(), 1);
+    t2.create_breed_link_with("friendships", &t0).unwrap();
+    let friendship = t0
+        .my_breed_links("friendships")
+        .values()
+        .next()
+        .unwrap()
+        .clone();
+    assert_eq!(friendship.who(), (0, 2));
+    assert_ne!(Some(friendship.clone()), t0.out_link_to(&t2));
+    assert_eq!(t0.breed_link_neighbors("friendships").count(), 1);
+    assert_eq!(w.borrow().links().len(), 5);
+
+    let state = w.borrow().rng_state();
+    let first = friendship.rng().u64(..);
+    t0.out_link_to(&t2).unwrap().rng().u64(..);
+    w.borrow_mut().set_rng_state(&state);
+    assert_eq!(friendship.rng().u64(..), first);
+}
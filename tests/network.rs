@@ -0,0 +1,86 @@
+use ruscape::prelude::*;
+
+fn degrees(turtles: &TurtleSet) -> Vec<usize> {
+    turtles.values().map(|t| t.my_links().count()).collect()
+}
+
+#[test]
+fn deterministic_networks() {
+    let w = World::init_with_seed(0, 10, 10, Center, true, 1);
+    let complete = w.borrow_mut().complete_network(5, None).unwrap();
+    assert_eq!(complete.len(), 5);
+    assert!(degrees(&complete).iter().all(|&d| d == 4));
+
+    let star = w.borrow_mut().star_network(6, None).unwrap();
+    let hub = star.values().next().unwrap().clone();
+    assert_eq!(hub.my_links().count(), 5);
+    assert!(star.all(|t| *t == hub || t.my_links().count() == 1));
+
+    let ring = w.borrow_mut().ring_lattice(8, 4, None).unwrap();
+    assert!(degrees(&ring).iter().all(|&d| d == 4));
+
+    let grid = w.borrow_mut().grid_lattice(4, 3, false, None).unwrap();
+    assert_eq!(degrees(&grid).iter().sum::<usize>(), 2 * (3 * 3 + 4 * 2));
+    let torus = w.borrow_mut().grid_lattice(4, 3, true, None).unwrap();
+    assert!(degrees(&torus).iter().all(|&d| d == 4));
+
+    assert_eq!(w.borrow().turtles().count(), 5 + 6 + 8 + 12 + 12);
+    assert_eq!(w.borrow().links().count(), 10 + 5 + 16 + 17 + 24);
+}
+
+#[test]
+fn random_networks() {
+    let w = World::init_with_seed(0, 10, 10, Center, true, 7);
+    let er = w.borrow_mut().erdos_renyi(50, 0.1, None).unwrap();
+    let links = degrees(&er).iter().sum::<usize>() / 2;
+    assert!(links > 60 && links < 190);
+
+    let ba = w.borrow_mut().barabasi_albert(100, 2, None).unwrap();
+    assert_eq!(degrees(&ba).iter().sum::<usize>(), 2 * 2 * 98);
+    assert!(degrees(&ba).iter().all(|&d| d >= 2));
+
+    let ws = w.borrow_mut().watts_strogatz(30, 4, 0.2, None).unwrap();
+    assert_eq!(degrees(&ws).iter().sum::<usize>(), 30 * 4);
+
+    let regular = w.borrow_mut().random_regular(20, 3, None).unwrap();
+    assert!(degrees(&regular).iter().all(|&d| d == 3));
+
+    let sbm = w
+        .borrow_mut()
+        .stochastic_block_model(&[5, 5], &[vec![1., 0.], vec![0., 1.]], None)
+        .unwrap();
+    assert!(degrees(&sbm).iter().all(|&d| d == 4));
+}
+
+#[test]
+fn networks_follow_the_world_seed() {
+    let build = || {
+        let w = World::init_with_seed(0, 10, 10, Center, true, 3);
+        let er = w.borrow_mut().erdos_renyi(30, 0.2, None).unwrap();
+        degrees(&er)
+    };
+    assert_eq!(build(), build());
+}
+
+#[test]
+fn networks_with_breeds_and_bad_parameters() {
+    let w = World::init(0, 10, 10, Center, true);
+    w.borrow_mut().declare_breed("nodes", &["score"]);
+    let nodes = w.borrow_mut().ring_lattice(6, 2, Some("nodes")).unwrap();
+    assert_eq!(w.borrow().breed("nodes").count(), 6);
+    assert!(nodes.all(|t| t.get("score") == Variable::default()));
+
+    assert_eq!(
+        w.borrow_mut().complete_network(3, Some("edges")).err(),
+        Some(RuscapeError::UnknownBreed("edges".to_string()))
+    );
+    assert!(w.borrow_mut().erdos_renyi(3, 1.5, None).is_err());
+    assert!(w.borrow_mut().barabasi_albert(3, 3, None).is_err());
+    assert!(w.borrow_mut().ring_lattice(5, 3, None).is_err());
+    assert!(w.borrow_mut().random_regular(5, 3, None).is_err());
+    assert!(w
+        .borrow_mut()
+        .stochastic_block_model(&[2, 2], &[vec![0.5]], None)
+        .is_err());
+    assert_eq!(w.borrow().turtles().count(), 6);
+}
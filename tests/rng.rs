@@ -0,0 +1,105 @@
+use ruscape::prelude::*;
+
+fn run(seed: u64) -> Vec<(usize, f64, f64)> {
+    let w = World::init_with_seed(100, 10, 10, Corner, true, seed);
+    let turtles = w.borrow().turtles();
+    turtles.ask(|t| {
+        t.random_headings().fd(3.);
+        t.move_in_radius(2.);
+    });
+    turtles
+        .n_of(10)
+        .report(|t| (t.xcor(), t.ycor()))
+        .into_iter()
+        .map(|(who, (x, y))| (who, x, y))
+        .collect()
+}
+
+#[test]
+fn same_seed_same_run() {
+    assert_eq!(run(42), run(42));
+    assert_ne!(run(42), run(43));
+}
+
+#[test]
+fn seed_is_reported() {
+    let w = World::init_with_seed(0, 10, 10, Corner, true, 7);
+    assert_eq!(w.borrow().seed(), 7);
+}
+
+#[test]
+fn agent_streams_ignore_unrelated_agents() {
+    let draws = |amount: usize| {
+        let w = World::init_with_seed(amount, 10, 10, Corner, true, 42);
+        let t = w.borrow().turtle(3);
+        let t2 = t.clone();
+        (t.rng().u64(..), t2.rng().u64(..))
+    };
+    assert_eq!(draws(5), draws(50));
+}
+
+#[test]
+fn restore_replays_random_sequence() {
+    let w = World::init_with_seed(20, 10, 10, Corner, true, 1);
+    let turtles = w.borrow().turtles();
+    let step = || {
+        turtles.ask(|t| {
+            t.random_headings().fd(t.rng().f64());
+        });
+        turtles.report(|t| (t.xcor(), t.ycor()))
+    };
+    let home = || {
+        turtles.ask(|t| {
+            t.home();
+        })
+    };
+    step();
+    home();
+    let state = w.borrow().rng_state();
+    let first = step();
+    home();
+    w.borrow_mut().set_rng_state(&state);
+    assert_eq!(step(), first);
+}
+
+#[test]
+fn distributions_follow_world_seed() {
+    let sample = |seed: u64| {
+        let w = World::init_with_seed(10, 10, 10, Corner, true, seed);
+        let turtles = w.borrow().turtles();
+        let w = w.borrow();
+        vec![
+            w.random_normal(0., 1.),
+            w.random_poisson(3.),
+            w.random_exponential(2.),
+            w.random_gamma(2., 1.),
+            w.random_binomial(10, 0.5) as f64,
+            turtles.random_float(5.),
+            turtles.one_of().unwrap().random_int(-10) as f64,
+        ]
+    };
+    assert_eq!(sample(9), sample(9));
+    let w = World::init_with_seed(0, 10, 10, Corner, true, 9);
+    let mean = (0..10000)
+        .map(|_| w.borrow().random_normal(5., 1.))
+        .sum::<f64>()
+        / 10000.;
+    assert!((mean - 5.).abs() < 0.1);
+}
+
+#[test]
+fn bad_distribution_parameters_are_errors() {
+    let w = World::init_with_seed(0, 10, 10, Corner, true, 9);
+    let w = w.borrow();
+    assert!(w.try_random_normal(0., -1.).is_err());
+    assert!(w.try_random_poisson(0.).is_err());
+    assert!(w.try_random_exponential(f64::NAN).is_err());
+    assert!(w.try_random_gamma(2., 0.).is_err());
+    assert_eq!(
+        w.try_random_binomial(10, 1.5),
+        Err(RuscapeError::InvalidDistribution(
+            "binomial needs a probability between 0 and 1".to_string()
+        ))
+    );
+    assert!(w.try_random_normal(5., 1.).is_ok());
+}
@@ -0,0 +1,44 @@
+use ruscape::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn events_run_in_time_and_priority_order() {
+    let w = World::init(3, 10, 10, Corner, true);
+    w.borrow_mut().reset_ticks();
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let push = |label: &'static str| {
+        let log = log.clone();
+        let w2 = w.clone();
+        move || log.borrow_mut().push((label, w2.borrow().ticks().unwrap()))
+    };
+    w.borrow_mut().schedule(2.5, 0, push("b")).unwrap();
+    w.borrow_mut().schedule(1.0, 1, push("a2")).unwrap();
+    w.borrow_mut().schedule(1.0, 0, push("a1")).unwrap();
+    let cancelled = w.borrow_mut().schedule(2.0, 0, push("x")).unwrap();
+    assert!(w.borrow_mut().cancel(cancelled));
+    w.borrow_mut().schedule(9.0, 0, push("late")).unwrap();
+
+    World::run_until(&w, 5.).unwrap();
+    assert_eq!(*log.borrow(), vec![("a1", 1.0), ("a2", 1.0), ("b", 2.5)]);
+    assert_eq!(w.borrow().ticks(), Ok(5.));
+    assert_eq!(w.borrow().next_event_time(), Some(9.));
+    assert!(w.borrow_mut().schedule(1.0, 0, || ()).is_err());
+    assert!(World::run_until(&w, f64::NAN).is_err());
+    assert!(World::run_until(&w, f64::INFINITY).is_err());
+    assert_eq!(w.borrow().ticks(), Ok(5.));
+}
+
+#[test]
+fn dying_cancels_turtle_events() {
+    let w = World::init(3, 10, 10, Corner, true);
+    w.borrow_mut().reset_ticks();
+    let t = w.borrow().turtle(1);
+    t.schedule(37.5, 0, |t| {
+        t.set_random_color();
+    })
+    .unwrap();
+    assert_eq!(w.borrow().pending_events(), 1);
+    t.die();
+    assert_eq!(w.borrow().pending_events(), 0);
+}
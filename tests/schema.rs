@@ -0,0 +1,47 @@
+use ruscape::prelude::*;
+
+#[test]
+fn typed_variables_reject_other_types() {
+    let w = World::init(3, 10, 10, Corner, true);
+    let mut turtles = w.borrow().turtles();
+    turtles.own_typed("energy", VarType::Float, 10.).unwrap();
+    turtles.own_typed("name", VarType::String, "bob").unwrap();
+    let t0 = w.borrow().turtle(0);
+    assert_eq!(t0.get("energy"), Variable::Float(10.));
+    assert!(t0.try_set("energy", 4.5).is_ok());
+    assert_eq!(
+        t0.try_set("energy", 5i64),
+        Err(RuscapeError::TypeMismatch {
+            agent: "0".to_string(),
+            variable: "energy".to_string(),
+            expected: VarType::Float,
+            found: VarType::Integer,
+        })
+    );
+    assert_eq!(t0.get("energy"), Variable::Float(4.5));
+    assert!(t0.try_set("payof", 1.).is_err());
+    assert!(t0.hatch(1).one_of().unwrap().try_set("name", true).is_err());
+}
+
+#[test]
+fn typed_defaults_must_match_their_type() {
+    let w = World::init(1, 10, 10, Corner, true);
+    let mut patches = w.borrow().patches();
+    assert!(patches.own_typed("grass", VarType::Integer, 0.5).is_err());
+    patches.own_typed("grass", VarType::Integer, 3i64).unwrap();
+    let p = w.borrow().patch(1., 1.);
+    assert_eq!(p.get("grass"), Variable::Integer(3));
+    assert!(p.try_set("grass", 4i64).is_ok());
+    assert!(p.try_set("grass", false).is_err());
+}
+
+#[test]
+fn untyped_variables_accept_anything() {
+    let w = World::init(1, 10, 10, Corner, true);
+    let mut turtles = w.borrow().turtles();
+    turtles.own(vec!["anything"]);
+    let t0 = w.borrow().turtle(0);
+    t0.set("anything", 1i64);
+    t0.set("anything", "text");
+    assert_eq!(t0.get("anything"), Variable::String("text".to_string()));
+}
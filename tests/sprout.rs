@@ -11,8 +11,7 @@ fn sprout_test() {
         t.patch_here().sprout(1);
     });
 
-    eprintln!(
-        " = {:#?}",
-        w.borrow().turtles().max_one_of(|t| t.who()).who()
-    );
+    let turtles = w.borrow().turtles();
+    assert_eq!(turtles.count(), 20000);
+    assert_eq!(turtles.max_one_of(|t| t.who()).unwrap().who(), 19999);
 }
@@ -0,0 +1,39 @@
+use ruscape::prelude::*;
+
+#[test]
+fn blinker_oscillates_with_synchronous_updates() {
+    let w = World::init(0, 4, 4, Corner, false);
+    let mut patches = w.borrow().patches();
+    patches.own(vec!["alive"]);
+    patches.ask(|p| p.set("alive", p.pycor() == 2 && (1..=3).contains(&p.pxcor())));
+
+    patches.ask_sync(|p| {
+        let n = p.neighbors().with(|n| n.get("alive") == true).count();
+        let alive = p.get("alive") == true;
+        p.set("alive", n == 3 || (alive && n == 2));
+    });
+
+    let alive = patches.with(|p| p.get("alive") == true);
+    let mut cells = alive.keys().cloned().collect::<Vec<_>>();
+    cells.sort();
+    assert_eq!(cells, vec![(2, 1), (2, 2), (2, 3)]);
+}
+
+#[test]
+fn panicking_ask_sync_leaves_no_buffered_writes() {
+    let w = World::init_with_seed(3, 5, 5, Corner, true, 1);
+    let mut turtles = w.borrow().turtles();
+    turtles.own(vec!["energy"]);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        turtles.ask_sync(|t| {
+            t.set("energy", 1);
+            if t.who() == 1 {
+                panic!("stop");
+            }
+        })
+    }));
+    assert!(result.is_err());
+    assert!(turtles.all(|t| t.get("energy") == Variable::default()));
+    turtles.ask(|t| t.set("energy", 2));
+    assert!(turtles.all(|t| t.get("energy") == 2));
+}
@@ -4,5 +4,5 @@ use ruscape::prelude::*;
 fn test_get_set() {
     let w = World::init(10000, 10, 10, Corner, true);
     let patches = w.borrow().patches();
-    eprintln!(" = {:#?}", patches.one_of().neighbors());
+    assert_eq!(patches.one_of().unwrap().neighbors().count(), 8);
 }
@@ -0,0 +1,23 @@
+use ruscape::prelude::*;
+
+#[test]
+fn ticks_require_reset() {
+    let w = World::init(10, 10, 10, Corner, true);
+    assert_eq!(w.borrow().ticks(), Err(RuscapeError::TicksNotStarted));
+    assert_eq!(w.borrow_mut().tick(), Err(RuscapeError::TicksNotStarted));
+
+    w.borrow_mut().reset_ticks();
+    w.borrow_mut().tick().unwrap();
+    w.borrow_mut().tick_advance(0.5).unwrap();
+    assert_eq!(w.borrow().ticks(), Ok(1.5));
+    assert!(w.borrow_mut().tick_advance(-1.).is_err());
+    assert!(w.borrow_mut().tick_advance(f64::NAN).is_err());
+    assert_eq!(
+        w.borrow_mut().tick_advance(f64::INFINITY),
+        Err(RuscapeError::EventInPast(f64::INFINITY))
+    );
+    assert_eq!(w.borrow().ticks(), Ok(1.5));
+
+    let t = w.borrow().turtle(0);
+    assert_eq!(t.ticks(), Ok(1.5));
+}
@@ -0,0 +1,95 @@
+use ruscape::prelude::*;
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn tied_turtles_follow_the_root() {
+    let w = World::init(3, 10, 10, Center, false);
+    let root = w.borrow().turtle(0);
+    let leaf = w.borrow().turtle(1);
+    let loose = w.borrow().turtle(2);
+    root.setxy(0., 0.);
+    leaf.setxy(0., 1.);
+    loose.setxy(1., 0.);
+    root.create_link_to(&leaf);
+    root.create_link_with(&loose);
+    let link = root.out_link_to(&leaf).unwrap();
+    assert_eq!(link.tie_mode(), TieMode::None);
+    link.tie();
+    assert_eq!(link.tie_mode(), TieMode::Fixed);
+
+    root.setxy(2., 3.);
+    assert!(close(leaf.xcor(), 2.) && close(leaf.ycor(), 4.));
+    assert!(close(loose.xcor(), 1.) && close(loose.ycor(), 0.));
+    assert_eq!(leaf.patch_here(), w.borrow().patch(2., 4.));
+
+    root.right(90.);
+    assert!(close(leaf.xcor(), 3.) && close(leaf.ycor(), 3.));
+    assert!(close(leaf.heading(), 90.));
+    root.fd(1.);
+    assert!(close(leaf.xcor(), 4.) && close(leaf.ycor(), 3.));
+    root.left(90.);
+    assert!(close(leaf.xcor(), 3.) && close(leaf.ycor(), 4.));
+    assert!(close(leaf.heading(), 0.));
+
+    leaf.setxy(0., 0.);
+    assert!(close(root.xcor(), 3.) && close(root.ycor(), 3.));
+
+    link.untie();
+    root.setxy(0., 0.);
+    assert!(close(leaf.xcor(), 0.) && close(leaf.ycor(), 0.));
+}
+
+#[test]
+fn free_ties_keep_headings_and_chains_move_rigidly() {
+    let w = World::init(3, 10, 10, Center, true);
+    let a = w.borrow().turtle(0);
+    let b = w.borrow().turtle(1);
+    let c = w.borrow().turtle(2);
+    a.setxy(0., 0.);
+    b.setxy(1., 0.);
+    c.setxy(2., 0.);
+    a.create_link_with(&b);
+    b.create_link_with(&c);
+    a.link_with(&b).unwrap().set_tie_mode(TieMode::Free);
+    b.link_with(&c).unwrap().tie();
+
+    a.right(90.);
+    assert!(close(b.xcor(), 0.) && close(b.ycor(), -1.));
+    assert!(close(c.xcor(), 1.) && close(c.ycor(), -1.));
+    assert!(close(b.heading(), 0.));
+    assert!(close(c.heading(), 0.));
+
+    c.setxy(0., 5.);
+    assert!(close(b.xcor(), -1.) && close(b.ycor(), 5.));
+    assert!(close(a.xcor(), -1.) && close(a.ycor(), 6.));
+}
+
+#[test]
+fn tied_moves_off_the_edge_are_refused() {
+    let w = World::init(3, 10, 10, Center, false);
+    let root = w.borrow().turtle(0);
+    let leaf = w.borrow().turtle(1);
+    let other = w.borrow().turtle(2);
+    root.setxy(9., 0.);
+    leaf.setxy(10., 0.);
+    root.create_link_with(&leaf);
+    root.link_with(&leaf).unwrap().tie();
+
+    assert_eq!(
+        root.try_setxy(10., 0.).err(),
+        Some(RuscapeError::TiedTurtleOutOfBounds(1))
+    );
+    assert!(close(root.xcor(), 9.) && close(leaf.xcor(), 10.));
+    assert!(root.try_left(90.).is_ok());
+    assert!(close(leaf.xcor(), 9.) && close(leaf.ycor(), 1.));
+
+    root.create_link_with(&other);
+    root.link_with(&other).unwrap().tie();
+    root.link_with(&leaf).unwrap().die();
+    other.die();
+    root.setxy(0., 0.);
+    assert!(close(leaf.xcor(), 9.) && close(leaf.ycor(), 1.));
+}
@@ -0,0 +1,67 @@
+use ruscape::prelude::*;
+use std::collections::BTreeMap;
+
+#[test]
+fn agents_can_hold_lists_and_maps() {
+    let w = World::init(2, 10, 10, Corner, true);
+    let mut turtles = w.borrow().turtles();
+    turtles.own(vec!["memory", "scores"]);
+    let t0 = w.borrow().turtle(0);
+    t0.set("memory", vec![1i64, 2, 3]);
+    let mut scores = BTreeMap::new();
+    scores.insert("cooperate".to_string(), Variable::Float(1.5));
+    t0.set("scores", scores.clone());
+
+    let t1 = t0.hatch(1).one_of().unwrap();
+    assert_eq!(t1.get("memory"), Variable::from(vec![1i64, 2, 3]));
+    t1.set("memory", Vec::<Variable>::new());
+    assert_eq!(t0.get("memory"), Variable::from(vec![1i64, 2, 3]));
+    assert_eq!(t1.get("scores"), Variable::Map(scores));
+}
+
+#[test]
+fn agent_references_follow_the_agent() {
+    let w = World::init(3, 10, 10, Corner, true);
+    let mut turtles = w.borrow().turtles();
+    turtles
+        .own_typed("partner", VarType::Turtle, Variable::Nobody)
+        .unwrap();
+    turtles.own(vec!["friends", "home"]);
+    let (t0, t1, t2) = (
+        w.borrow().turtle(0),
+        w.borrow().turtle(1),
+        w.borrow().turtle(2),
+    );
+    assert!(t0.get("partner").is_nobody());
+    t0.set("partner", t1.clone());
+    t0.set("home", t0.patch_here());
+    t0.set("friends", t0.other(&turtles));
+    assert!(t0.try_set("partner", 1.).is_err());
+    assert!(t0.try_set("partner", t0.patch_here()).is_err());
+
+    t1.fd(2.);
+    match t0.get("partner") {
+        Variable::Turtle(partner) => assert_eq!(partner.xcor(), t1.xcor()),
+        other => panic!("expected a turtle, got {:?}", other.var_type()),
+    }
+    assert_eq!(t0.get("partner"), Variable::Turtle(t1.clone()));
+    assert_eq!(t0.get("home"), Variable::Patch(w.borrow().patch(0., 0.)));
+    assert_eq!(
+        t0.get("friends"),
+        Variable::TurtleSet(turtles.with(|t| t.who() != 0))
+    );
+
+    t1.set("partner", t0.clone());
+    t1.die();
+    let dead = Some(RuscapeError::DeadAgent("1".to_string()));
+    assert_eq!(t1.try_get("partner").err(), dead.clone());
+    assert_eq!(t1.try_set("partner", Variable::Nobody).err(), dead);
+    assert!(t0.get("partner").is_nobody());
+    assert_eq!(t0.get("partner"), Variable::Nobody);
+    t2.die();
+    assert_eq!(t0.get("friends"), Variable::from(TurtleSet::default()));
+    assert_eq!(
+        Variable::from(turtles.max_one_of(|t| t.who())),
+        Variable::Turtle(t0)
+    );
+}
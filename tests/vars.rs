@@ -0,0 +1,89 @@
+use ruscape::prelude::*;
+
+#[derive(Clone, Default, TurtleVars)]
+struct Sheep {
+    energy: f64,
+    age: u32,
+}
+
+#[derive(Clone, PatchVars)]
+struct Grass {
+    grown: bool,
+}
+
+#[test]
+fn typed_vars_have_generated_accessors() {
+    let w = World::init(3, 10, 10, Corner, true);
+    let mut turtles = w.borrow().turtles();
+    turtles.own_vars(|t| Sheep {
+        energy: t.who() as f64,
+        age: 0,
+    });
+    let t2 = w.borrow().turtle(2);
+    assert_eq!(t2.get_energy(), 2.);
+    t2.set_energy(t2.get_energy() + 1.5);
+    t2.vars_mut::<Sheep>().age += 1;
+    assert_eq!(t2.vars::<Sheep>().energy, 3.5);
+    assert_eq!(t2.get_age(), 1);
+
+    let lamb = t2.hatch(1).one_of().unwrap();
+    assert_eq!(lamb.get_energy(), 3.5);
+    lamb.set_age(0);
+    assert_eq!(t2.get_age(), 1);
+    assert!(t2.try_vars::<Grass>().is_err());
+}
+
+#[test]
+fn typed_vars_are_visible_as_variables() {
+    let w = World::init(1, 10, 10, Corner, true);
+    let mut patches = w.borrow().patches();
+    patches.own_vars(|_| Grass { grown: true });
+    let t0 = w.borrow().turtle(0);
+    t0.attach_vars(Sheep::default());
+
+    let p = t0.patch_here();
+    assert_eq!(p.get("grown"), Variable::Boolean(true));
+    p.set("grown", false);
+    assert!(!p.get_grown());
+
+    t0.set("age", 4i64);
+    assert_eq!(t0.get_age(), 4);
+    assert_eq!(t0.get_f64("energy"), 0.);
+    assert_eq!(
+        t0.try_set("age", -1i64),
+        Err(RuscapeError::IntegerOutOfRange(-1))
+    );
+    assert_eq!(
+        t0.try_set("energy", "lots"),
+        Err(RuscapeError::TypeMismatch {
+            agent: "0".to_string(),
+            variable: "energy".to_string(),
+            expected: VarType::Float,
+            found: VarType::String,
+        })
+    );
+    assert!(t0.try_set("wool", 1.).is_err());
+    assert_eq!(t0.vars::<Sheep>().var_names(), &["energy", "age"]);
+}
+
+#[test]
+fn typed_writes_wait_for_the_end_of_ask_sync() {
+    let w = World::init_with_seed(3, 10, 10, Corner, true, 1);
+    let mut turtles = w.borrow().turtles();
+    turtles.own_vars(|t| Sheep {
+        energy: t.who() as f64,
+        age: 0,
+    });
+    let all = turtles.clone();
+    turtles.ask_sync(|t| {
+        let most = all
+            .values()
+            .map(|other| other.get_energy())
+            .fold(0., f64::max);
+        t.set_energy(most + 1.);
+        t.set("age", 2i64);
+        assert!(t.try_set("age", "old").is_err());
+        assert_eq!(t.get_age(), 0);
+    });
+    assert!(turtles.all(|t| t.get_energy() == 3. && t.get_age() == 2));
+}